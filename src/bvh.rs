@@ -0,0 +1,199 @@
+use crate::geometry::Aabb;
+use crate::objects::{HitRecord, Object, PackedHitRecords};
+use crate::ray::{PackedRays, Ray};
+use std::simd::{LaneCount, SupportedLaneCount};
+
+/// Leaves stop splitting once they hold this many or fewer objects; below this
+/// size a linear scan is cheaper than another level of box tests.
+const LEAF_SIZE: usize = 4;
+
+/// Target bucket count for the surface-area-heuristic split search.
+const SAH_BUCKETS: usize = 12;
+
+#[derive(Debug)]
+#[derive(Clone)]
+enum BvhNodeKind {
+    Leaf(Vec<usize>),
+    Internal(Box<BvhNode>, Box<BvhNode>),
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+struct BvhNode {
+    bounds: Aabb,
+    kind: BvhNodeKind,
+}
+
+impl BvhNode {
+    /// Builds a subtree over `indices`, sorting by centroid along the bounding
+    /// box's longest axis and splitting at the boundary `sah_split` picks
+    /// (falling back to the median when it can't choose). `indices` is
+    /// reordered in place.
+    fn build(indices: &mut [usize], objects: &[Object]) -> BvhNode {
+        let bounds = indices.iter()
+            .map(|&i| objects[i].aabb())
+            .fold(Aabb::empty(), |acc, b| acc.union(&b));
+
+        if indices.len() <= LEAF_SIZE {
+            return BvhNode { bounds, kind: BvhNodeKind::Leaf(indices.to_vec()) };
+        }
+
+        let axis = bounds.longest_axis();
+        indices.sort_by(|&a, &b| {
+            let centroid_a = objects[a].aabb().centroid();
+            let centroid_b = objects[b].aabb().centroid();
+            let key_a = match axis { 0 => centroid_a.x(), 1 => centroid_a.y(), _ => centroid_a.z() };
+            let key_b = match axis { 0 => centroid_b.x(), 1 => centroid_b.y(), _ => centroid_b.z() };
+            ordered_float::OrderedFloat::from(key_a).cmp(&ordered_float::OrderedFloat::from(key_b))
+        });
+
+        let mid = BvhNode::sah_split(indices, objects).unwrap_or(indices.len() / 2);
+        let (left_indices, right_indices) = indices.split_at_mut(mid);
+
+        let left = Box::new(BvhNode::build(left_indices, objects));
+        let right = Box::new(BvhNode::build(right_indices, objects));
+
+        BvhNode { bounds, kind: BvhNodeKind::Internal(left, right) }
+    }
+
+    /// Surface-area-heuristic split point over `indices`, already sorted along
+    /// the chosen axis: bins them into up to `SAH_BUCKETS` equal-size groups,
+    /// evaluates `area(left) * count(left) + area(right) * count(right)` at
+    /// every bucket boundary, and returns the index of the cheapest one.
+    /// `None` when there are too few buckets to choose between (falls back
+    /// to a plain median split).
+    fn sah_split(indices: &[usize], objects: &[Object]) -> Option<usize> {
+        let bucket_count = SAH_BUCKETS.min(indices.len());
+        if bucket_count < 2 {
+            return None;
+        }
+
+        let bucket_size = (indices.len() + bucket_count - 1) / bucket_count;
+
+        let bucket_bounds: Vec<Aabb> = indices.chunks(bucket_size)
+            .map(|chunk| chunk.iter().map(|&i| objects[i].aabb()).fold(Aabb::empty(), |acc, b| acc.union(&b)))
+            .collect();
+
+        if bucket_bounds.len() < 2 {
+            return None;
+        }
+
+        let prefix_bounds: Vec<Aabb> = bucket_bounds.iter()
+            .scan(Aabb::empty(), |acc, b| { *acc = acc.union(b); Some(*acc) })
+            .collect();
+
+        let suffix_bounds: Vec<Aabb> = {
+            let mut reversed: Vec<Aabb> = bucket_bounds.iter().rev()
+                .scan(Aabb::empty(), |acc, b| { *acc = acc.union(b); Some(*acc) })
+                .collect();
+            reversed.reverse();
+            reversed
+        };
+
+        (1..bucket_bounds.len())
+            .map(|boundary| {
+                let left_count = boundary * bucket_size;
+                let right_count = indices.len() - left_count;
+                let cost = prefix_bounds[boundary - 1].surface_area() * (left_count as f64)
+                    + suffix_bounds[boundary].surface_area() * (right_count as f64);
+                (boundary * bucket_size, cost)
+            })
+            .min_by(|(_, a), (_, b)| ordered_float::OrderedFloat::from(*a).cmp(&ordered_float::OrderedFloat::from(*b)))
+            .map(|(split, _)| split)
+    }
+
+    fn hit<'a>(&self, objects: &'a [Object], ray: &Ray, t_range: &std::ops::Range<f64>) -> Option<HitRecord<'a>> {
+        if !self.bounds.hit_scalar(ray.origin(), ray.direction(), t_range) {
+            return None;
+        }
+
+        match &self.kind {
+            BvhNodeKind::Leaf(indices) => {
+                indices.iter()
+                    .filter_map(|&i| objects[i].hit(ray, t_range))
+                    .min_by_key(|h| ordered_float::OrderedFloat::from(h.t()))
+            },
+            BvhNodeKind::Internal(left, right) => {
+                let left_hit = left.hit(objects, ray, t_range);
+                let narrowed_range = match &left_hit {
+                    Some(h) => t_range.start..h.t(),
+                    None => t_range.clone(),
+                };
+                let right_hit = right.hit(objects, ray, &narrowed_range);
+
+                right_hit.or(left_hit)
+            },
+        }
+    }
+
+    /// Visits this node whenever any enabled lane hits its box, narrowing the
+    /// surviving lane mask down through `PackedRays::enabled` as it descends.
+    fn hit_packed<'a, const N: usize>(&'a self, objects: &'a [Object], rays: &PackedRays<N>, t_range: &std::ops::Range<f64>, hit_records: &mut PackedHitRecords<'a, N>)
+    where LaneCount<N>: SupportedLaneCount
+    {
+        let box_mask = self.bounds.hit_packed(&rays.origins(), &rays.directions(), t_range) & rays.enabled();
+
+        if !box_mask.any() {
+            return;
+        }
+
+        let narrowed_rays = PackedRays::new_with_enable_and_time(rays.origins(), rays.directions(), box_mask, rays.time());
+
+        match &self.kind {
+            BvhNodeKind::Leaf(indices) => {
+                for &i in indices {
+                    objects[i].hit_packed(&narrowed_rays, t_range, hit_records);
+                }
+            },
+            BvhNodeKind::Internal(left, right) => {
+                left.hit_packed(objects, &narrowed_rays, t_range, hit_records);
+                right.hit_packed(objects, &narrowed_rays, t_range, hit_records);
+            },
+        }
+    }
+}
+
+/// Binary BVH over a flat object list, tested with the slab method instead of
+/// the linear per-object scan the tile renderer used to do.
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Bvh {
+    objects: Vec<Object>,
+    root: Option<BvhNode>,
+}
+
+impl Bvh {
+    pub fn build(objects: Vec<Object>) -> Bvh {
+        let mut indices: Vec<usize> = (0..objects.len()).collect();
+        let root = if indices.is_empty() {
+            None
+        } else {
+            Some(BvhNode::build(&mut indices, &objects))
+        };
+
+        Bvh { objects, root }
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Appends an object and rebuilds the tree from scratch.
+    pub fn push(&mut self, object: Object) {
+        self.objects.push(object);
+        let mut indices: Vec<usize> = (0..self.objects.len()).collect();
+        self.root = Some(BvhNode::build(&mut indices, &self.objects));
+    }
+
+    pub fn hit(&self, ray: &Ray, t_range: &std::ops::Range<f64>) -> Option<HitRecord> {
+        self.root.as_ref().and_then(|root| root.hit(&self.objects, ray, t_range))
+    }
+
+    pub fn hit_packed<'a, const N: usize>(&'a self, rays: &PackedRays<N>, t_range: &std::ops::Range<f64>, hit_records: &mut PackedHitRecords<'a, N>)
+    where LaneCount<N>: SupportedLaneCount
+    {
+        if let Some(root) = &self.root {
+            root.hit_packed(&self.objects, rays, t_range, hit_records);
+        }
+    }
+}
@@ -1,8 +1,226 @@
 use crate::{toml_utils::to_float, simd_util::SimdPermute};
 
-use std::simd::{Simd, Mask, LaneCount, SupportedLaneCount, cmp::SimdPartialOrd, SimdElement};
+use std::simd::{Simd, Mask, LaneCount, SupportedLaneCount, cmp::SimdPartialOrd, SimdElement, num::SimdFloat};
 use array_macro::array;
 use crate::simd_util::masked_assign;
+use crate::simd_util::simd_powf;
+
+/// Maps linear HDR radiance down to the `[0, 1]` range a display can show,
+/// applied before the output transfer function in `Color::tone_mapped_u8_array`.
+/// Unlike a plain clamp, the non-`Clamp` operators compress rather than clip
+/// values above 1.0, which matters for bright emitters and many-bounce paths
+/// that can easily exceed it.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq)]
+pub enum ToneMap {
+    /// Clip to `[0, 1]` with no compression.
+    Clamp,
+    /// `c / (1 + c)`.
+    Reinhard,
+    /// `c * (1 + c / white^2) / (1 + c)` -- like `Reinhard`, but `white` maps
+    /// to exactly 1.0; values above `white` map above 1.0 (no clipping here).
+    ExtendedReinhard { white: f64 },
+    /// Narkowicz's ACES filmic fit.
+    AcesFilmic,
+}
+
+impl ToneMap {
+    pub fn apply(&self, c: f64) -> f64 {
+        match self {
+            ToneMap::Clamp => c.clamp(0.0, 1.0),
+            ToneMap::Reinhard => c / (1.0 + c),
+            ToneMap::ExtendedReinhard { white } => c * (1.0 + c / (white * white)) / (1.0 + c),
+            ToneMap::AcesFilmic => ((c * (2.51 * c + 0.03)) / (c * (2.43 * c + 0.59) + 0.14)).clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn apply_packed<const N: usize>(&self, c: Simd<f64, N>) -> Simd<f64, N>
+    where LaneCount<N>: SupportedLaneCount
+    {
+        match self {
+            ToneMap::Clamp => c.simd_max(Simd::splat(0.0)).simd_min(Simd::splat(1.0)),
+            ToneMap::Reinhard => c / (Simd::splat(1.0) + c),
+            ToneMap::ExtendedReinhard { white } => {
+                let white_squared = Simd::splat(white * white);
+                c * (Simd::splat(1.0) + c / white_squared) / (Simd::splat(1.0) + c)
+            },
+            ToneMap::AcesFilmic => {
+                let mapped = (c * (Simd::splat(2.51) * c + Simd::splat(0.03))) / (c * (Simd::splat(2.43) * c + Simd::splat(0.59)) + Simd::splat(0.14));
+                mapped.simd_max(Simd::splat(0.0)).simd_min(Simd::splat(1.0))
+            },
+        }
+    }
+}
+
+/// Output transfer function applied after tone mapping, converting the
+/// compressed-to-`[0, 1]` linear value into the nonlinear space a display
+/// expects.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq)]
+pub enum TransferFunction {
+    /// `c^(1/gamma)`. `Gamma(2.0)` matches this crate's historical `sqrt` curve.
+    Gamma(f64),
+    /// True sRGB piecewise curve (linear segment near black, power curve above).
+    Srgb,
+}
+
+impl TransferFunction {
+    pub fn apply(&self, c: f64) -> f64 {
+        match self {
+            TransferFunction::Gamma(gamma) => c.powf(1.0 / gamma),
+            TransferFunction::Srgb => {
+                if c <= 0.0031308 {
+                    12.92 * c
+                } else {
+                    1.055 * c.powf(1.0 / 2.4) - 0.055
+                }
+            },
+        }
+    }
+
+    pub fn apply_packed<const N: usize>(&self, c: Simd<f64, N>) -> Simd<f64, N>
+    where LaneCount<N>: SupportedLaneCount
+    {
+        match self {
+            TransferFunction::Gamma(gamma) => simd_powf(c, Simd::splat(1.0 / gamma)),
+            TransferFunction::Srgb => {
+                let linear = c * Simd::splat(12.92);
+                let gamma = simd_powf(c, Simd::splat(1.0 / 2.4)) * Simd::splat(1.055) - Simd::splat(0.055);
+                c.simd_le(Simd::splat(0.0031308)).select(linear, gamma)
+            },
+        }
+    }
+}
+
+/// Output pixel encoding, applied after tone mapping and the transfer
+/// function. `Rgb` is this crate's historical output; `YCbCr` separates
+/// luma from chroma using the BT.709 coefficients, the encoding most video
+/// pipelines expect downstream of a renderer.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub enum ColorSpace {
+    Rgb,
+    YCbCr,
+}
+
+/// Flash-style linear color grade: `c' = c * mult + add`, per channel.
+/// `exposure`/`white_balance` are just convenience constructors for the
+/// multiply-only case.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq)]
+pub struct ColorTransform {
+    pub r_mult: f64,
+    pub g_mult: f64,
+    pub b_mult: f64,
+    pub r_add: f64,
+    pub g_add: f64,
+    pub b_add: f64,
+}
+
+impl ColorTransform {
+    pub fn identity() -> ColorTransform {
+        ColorTransform { r_mult: 1.0, g_mult: 1.0, b_mult: 1.0, r_add: 0.0, g_add: 0.0, b_add: 0.0 }
+    }
+
+    /// Uniform gain in photographic stops -- `exposure(1.0)` doubles brightness.
+    pub fn exposure(stops: f64) -> ColorTransform {
+        ColorTransform::white_balance(2f64.powf(stops), 2f64.powf(stops), 2f64.powf(stops))
+    }
+
+    /// Independent per-channel gain, no offset -- lets a warm/cool tint be
+    /// corrected (or introduced) without touching `exposure`.
+    pub fn white_balance(r_gain: f64, g_gain: f64, b_gain: f64) -> ColorTransform {
+        ColorTransform { r_mult: r_gain, g_mult: g_gain, b_mult: b_gain, r_add: 0.0, g_add: 0.0, b_add: 0.0 }
+    }
+
+    pub fn apply(&self, c: Color) -> Color {
+        c * Color::new(self.r_mult, self.g_mult, self.b_mult) + Color::new(self.r_add, self.g_add, self.b_add)
+    }
+
+    pub fn apply_packed<const N: usize>(&self, c: PackedColor<N>) -> PackedColor<N>
+    where LaneCount<N>: SupportedLaneCount
+    {
+        c * PackedColor::splat(Color::new(self.r_mult, self.g_mult, self.b_mult)) + PackedColor::splat(Color::new(self.r_add, self.g_add, self.b_add))
+    }
+}
+
+/// Channel isolation/swizzle: zeroes out any of R/G/B not selected, e.g. to
+/// extract a single channel as a grayscale-on-that-channel debug AOV.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub struct ChannelOptions {
+    pub red: bool,
+    pub green: bool,
+    pub blue: bool,
+}
+
+impl ChannelOptions {
+    pub fn all() -> ChannelOptions {
+        ChannelOptions { red: true, green: true, blue: true }
+    }
+
+    fn mask(&self) -> Color {
+        Color::new(self.red as u8 as f64, self.green as u8 as f64, self.blue as u8 as f64)
+    }
+
+    pub fn apply(&self, c: Color) -> Color {
+        c * self.mask()
+    }
+
+    pub fn apply_packed<const N: usize>(&self, c: PackedColor<N>) -> PackedColor<N>
+    where LaneCount<N>: SupportedLaneCount
+    {
+        c * PackedColor::splat(self.mask())
+    }
+}
+
+/// One stage of a color-grading stack (see `apply_color_ops`).
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq)]
+pub enum ColorOp {
+    Transform(ColorTransform),
+    Channels(ChannelOptions),
+}
+
+impl ColorOp {
+    pub fn apply(&self, c: Color) -> Color {
+        match self {
+            ColorOp::Transform(transform) => transform.apply(c),
+            ColorOp::Channels(channels) => channels.apply(c),
+        }
+    }
+
+    pub fn apply_packed<const N: usize>(&self, c: PackedColor<N>) -> PackedColor<N>
+    where LaneCount<N>: SupportedLaneCount
+    {
+        match self {
+            ColorOp::Transform(transform) => transform.apply_packed(c),
+            ColorOp::Channels(channels) => channels.apply_packed(c),
+        }
+    }
+}
+
+/// Runs a color-grading operator stack over `c` in order, so a render can be
+/// graded (exposure, white balance, isolating a channel to debug an AOV)
+/// without re-rendering the scene.
+pub fn apply_color_ops(c: Color, ops: &[ColorOp]) -> Color {
+    ops.iter().fold(c, |acc, op| op.apply(acc))
+}
+
+/// Lane-wise equivalent of `apply_color_ops`, kept vectorized the same way
+/// `PackedColor::tone_mapped` is. `render_vectorized` does not call it: it
+/// sums each pixel's samples into a scalar `Color` and runs `apply_color_ops`.
+pub fn apply_color_ops_packed<const N: usize>(c: PackedColor<N>, ops: &[ColorOp]) -> PackedColor<N>
+where LaneCount<N>: SupportedLaneCount
+{
+    ops.iter().fold(c, |acc, op| op.apply_packed(acc))
+}
 
 #[derive(Debug)]
 #[derive(Clone, Copy)]
@@ -52,20 +270,71 @@ impl Color {
     //     return [ir, ig, ib];
     // }
 
-    pub fn to_u8_array(&self) -> [u8; 3]{
-        assert!(self.red <= 2.0, "red should be less than 1.0, but got {}", self.red);
-        assert!(self.green <= 2.0, "green should be less than 1.0, but got {}", self.green);
-        assert!(self.blue <= 2.0, "blue should be less than 1.0, but got {}", self.blue);
+    /// Applies `tone_map` in linear space, then `transfer`, then scales to `[0, 255]`.
+    /// Unlike the old hard-coded gamma-2.0 curve this replaces, nothing here
+    /// asserts an input range -- `tone_map` is responsible for bringing
+    /// arbitrary HDR radiance down to `[0, 1]` before `transfer` runs.
+    pub fn tone_mapped_u8_array(&self, tone_map: ToneMap, transfer: TransferFunction) -> [u8; 3] {
         let scale_factor = 255.999;
-        let ir = (self.red.sqrt() * scale_factor) as u8;
-        let ig = (self.green.sqrt() * scale_factor) as u8;
-        let ib = (self.blue.sqrt() * scale_factor) as u8;
+        let map_channel = |c: f64| (transfer.apply(tone_map.apply(c)) * scale_factor) as u8;
 
-        return [ir, ig, ib];
+        [map_channel(self.red), map_channel(self.green), map_channel(self.blue)]
+    }
+
+    /// Like `tone_mapped_u8_array`, but packs the result as BT.709 YCbCr
+    /// instead of RGB: `Y = 0.2126 R' + 0.7152 G' + 0.0722 B'`, with Cb/Cr
+    /// the B'-Y/R'-Y differences scaled into `[0, 255]` around a 128 mid-gray.
+    pub fn tone_mapped_output_u8_array(&self, tone_map: ToneMap, transfer: TransferFunction, color_space: ColorSpace) -> [u8; 3] {
+        let [r, g, b] = self.tone_mapped_u8_array(tone_map, transfer);
+
+        match color_space {
+            ColorSpace::Rgb => [r, g, b],
+            ColorSpace::YCbCr => {
+                let (r, g, b) = (r as f64, g as f64, b as f64);
+                let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+                let cb = (b - y) / (2.0 * (1.0 - 0.0722)) + 128.0;
+                let cr = (r - y) / (2.0 * (1.0 - 0.2126)) + 128.0;
+
+                [y.round().clamp(0.0, 255.0) as u8, cb.round().clamp(0.0, 255.0) as u8, cr.round().clamp(0.0, 255.0) as u8]
+            },
+        }
+    }
+
+    pub fn to_u8_array(&self) -> [u8; 3]{
+        self.tone_mapped_u8_array(ToneMap::Clamp, TransferFunction::Gamma(2.0))
+    }
+
+    /// Per-channel `e^x`, used for Beer-Lambert transmission: `exp(-absorption * distance)`.
+    pub fn exp(&self) -> Color {
+        Color::new(self.red.exp(), self.green.exp(), self.blue.exp())
+    }
+
+    /// Reconstruction-filter weighted average: `sum(weight * color) / sum(weight)`,
+    /// used instead of a plain `average` when samples carry per-sample filter weights.
+    pub fn weighted_average<F>(iter: F) -> Color
+    where F: IntoIterator<Item=(f64, Color)>
+    {
+        let mut red = 0.0;
+        let mut green = 0.0;
+        let mut blue = 0.0;
+        let mut weight_sum = 0.0;
+
+        for (weight, color) in iter {
+            weight_sum += weight;
+            red += weight * color.red;
+            green += weight * color.green;
+            blue += weight * color.blue;
+        }
+
+        Color {
+            red: red / weight_sum,
+            green: green / weight_sum,
+            blue: blue / weight_sum,
+        }
     }
 
     pub fn average<F>(iter: F) -> Color
-    where F: IntoIterator<Item=Color> 
+    where F: IntoIterator<Item=Color>
     {
         let mut red = 0.0;
         let mut green = 0.0;
@@ -152,6 +421,19 @@ impl std::ops::Mul<f64> for Color {
     }
 }
 
+impl std::ops::Add<Color> for Color {
+    type Output = Color;
+
+    #[inline]
+    fn add(self, rhs: Color) -> Self::Output {
+        Color{
+            red: self.red + rhs.red,
+            green: self.green + rhs.green,
+            blue: self.blue + rhs.blue,
+        }
+    }
+}
+
 impl std::ops::Div<f64> for Color {
     type Output = Color;
 
@@ -232,6 +514,18 @@ where LaneCount<N>: SupportedLaneCount
         }
     }
 
+    /// Lane-wise `ToneMap` + `TransferFunction`, so a whole packet of final
+    /// pixel colors can be converted without falling back to scalar `Color`
+    /// for each lane (see `Color::tone_mapped_u8_array` for the scalar path).
+    #[inline]
+    pub fn tone_mapped(&self, tone_map: ToneMap, transfer: TransferFunction) -> PackedColor<N> {
+        PackedColor {
+            red: transfer.apply_packed(tone_map.apply_packed(self.red)),
+            green: transfer.apply_packed(tone_map.apply_packed(self.green)),
+            blue: transfer.apply_packed(tone_map.apply_packed(self.blue)),
+        }
+    }
+
     #[inline]
     pub fn check(&self) {
         assert!((self.red.simd_lt(Simd::splat(1.01))).all(), "RED Expect <= 1.0, got{:?}", self.red);
@@ -0,0 +1,45 @@
+/// Pixel reconstruction filters for combining jittered per-pixel samples.
+///
+/// Each sample's subpixel offset `(dx, dy)`, in pixel units measured from the
+/// pixel center, is assigned a `weight`; the pixel's final color is
+/// `sum(weight * color) / sum(weight)` (see `Color::weighted_average`) instead
+/// of a plain average. Each sample still contributes only to the pixel it
+/// was generated for, but its offset is drawn out to `radius()` from the
+/// pixel center, so `Tent` and `Gaussian` samples reach past the pixel's
+/// edge and are de-weighted the farther out they land.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq)]
+pub enum Filter {
+    Box,
+    Tent,
+    Gaussian,
+}
+
+impl Filter {
+    /// Half-width, in pixels, of the region samples are drawn from.
+    pub fn radius(&self) -> f64 {
+        match self {
+            Filter::Box => 0.5,
+            Filter::Tent => 1.0,
+            Filter::Gaussian => 2.0,
+        }
+    }
+
+    pub fn weight(&self, dx: f64, dy: f64) -> f64 {
+        match self {
+            Filter::Box => 1.0,
+            Filter::Tent => (1.0 - dx.abs()).max(0.0) * (1.0 - dy.abs()).max(0.0),
+            Filter::Gaussian => {
+                const ALPHA: f64 = 2.0;
+                let r_squared = self.radius() * self.radius();
+                let d_squared = dx * dx + dy * dy;
+                if d_squared > r_squared {
+                    0.0
+                } else {
+                    (-ALPHA * d_squared).exp() - (-ALPHA * r_squared).exp()
+                }
+            },
+        }
+    }
+}
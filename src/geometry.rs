@@ -1,8 +1,9 @@
 use rand::prelude::*;
 use crate::toml_utils::to_float;
 use array_macro::array;
-use std::{simd::{LaneCount, SupportedLaneCount, StdFloat, Simd, Mask, SimdElement, MaskElement}, ops::Mul};
-use crate::simd_util::masked_assign;
+use std::{simd::{LaneCount, SupportedLaneCount, StdFloat, Simd, Mask, SimdElement, MaskElement, num::SimdFloat, cmp::SimdPartialOrd}, ops::Mul};
+use crate::simd_util::{masked_assign, simd_sin, simd_cos};
+use crate::simd_rng::PackedRng;
 
 #[derive(Debug)]
 #[derive(Clone, Copy)]
@@ -13,6 +14,12 @@ pub struct Vec3 {
 }
 
 impl Vec3 {
+    pub const ZERO: Vec3 = Vec3 { x: 0.0, y: 0.0, z: 0.0 };
+    pub const ONE: Vec3 = Vec3 { x: 1.0, y: 1.0, z: 1.0 };
+    pub const X: Vec3 = Vec3 { x: 1.0, y: 0.0, z: 0.0 };
+    pub const Y: Vec3 = Vec3 { x: 0.0, y: 1.0, z: 0.0 };
+    pub const Z: Vec3 = Vec3 { x: 0.0, y: 0.0, z: 1.0 };
+
     pub fn new(x: f64, y: f64, z: f64) -> Vec3{
         Vec3 { x: x, y: y, z: z}
     }
@@ -21,6 +28,30 @@ impl Vec3 {
         Vec3 {x: 0.0, y: 0.0, z: 0.0}
     }
 
+    pub fn min(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
+
+    pub fn max(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
+
+    pub fn clamp(&self, min: &Vec3, max: &Vec3) -> Vec3 {
+        Vec3::new(self.x.clamp(min.x, max.x), self.y.clamp(min.y, max.y), self.z.clamp(min.z, max.z))
+    }
+
+    pub fn lerp(&self, other: &Vec3, t: f64) -> Vec3 {
+        *self + (*other - *self) * t
+    }
+
+    pub fn min_element(&self) -> f64 {
+        self.x.min(self.y).min(self.z)
+    }
+
+    pub fn max_element(&self) -> f64 {
+        self.x.max(self.y).max(self.z)
+    }
+
     pub fn x(&self) -> f64 {
         self.x
     }
@@ -111,8 +142,11 @@ impl Vec3 {
         self.length_squared().sqrt()
     }
 
-    pub fn abs(&self) -> f64 {
-        self.length()
+    /// Component-wise absolute value. Previously returned `length()`, which
+    /// was surprising given every other vector math crate treats `abs` as
+    /// per-component; use `length()` directly for the magnitude.
+    pub fn abs(&self) -> Vec3 {
+        Vec3::new(self.x.abs(), self.y.abs(), self.z.abs())
     }
 
     pub fn unit(self) -> Self {
@@ -214,6 +248,347 @@ impl Vec3 {
 
 pub type Point3 = Vec3;
 
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub struct Mat4 {
+    rows: [[f64; 4]; 4],
+}
+
+impl Mat4 {
+    pub fn new(rows: [[f64; 4]; 4]) -> Mat4 {
+        Mat4 { rows }
+    }
+
+    pub fn element(&self, row: usize, col: usize) -> f64 {
+        self.rows[row][col]
+    }
+
+    pub fn identity() -> Mat4 {
+        Mat4::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn translation(offset: Vec3) -> Mat4 {
+        Mat4::new([
+            [1.0, 0.0, 0.0, offset.x()],
+            [0.0, 1.0, 0.0, offset.y()],
+            [0.0, 0.0, 1.0, offset.z()],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn scaling(factor: Vec3) -> Mat4 {
+        Mat4::new([
+            [factor.x(), 0.0, 0.0, 0.0],
+            [0.0, factor.y(), 0.0, 0.0],
+            [0.0, 0.0, factor.z(), 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn rotation_x(angle_radians: f64) -> Mat4 {
+        let (s, c) = angle_radians.sin_cos();
+        Mat4::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, c, -s, 0.0],
+            [0.0, s, c, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn rotation_y(angle_radians: f64) -> Mat4 {
+        let (s, c) = angle_radians.sin_cos();
+        Mat4::new([
+            [c, 0.0, s, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [-s, 0.0, c, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn rotation_z(angle_radians: f64) -> Mat4 {
+        let (s, c) = angle_radians.sin_cos();
+        Mat4::new([
+            [c, -s, 0.0, 0.0],
+            [s, c, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn mul(&self, rhs: &Mat4) -> Mat4 {
+        let mut result = [[0.0; 4]; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                let mut sum = 0.0;
+                for k in 0..4 {
+                    sum += self.rows[i][k] * rhs.rows[k][j];
+                }
+                result[i][j] = sum;
+            }
+        }
+        Mat4::new(result)
+    }
+
+    /// Transforms a point, applying translation (implicit w = 1).
+    pub fn transform_point(&self, point: Point3) -> Point3 {
+        Vec3::new(
+            self.rows[0][0] * point.x() + self.rows[0][1] * point.y() + self.rows[0][2] * point.z() + self.rows[0][3],
+            self.rows[1][0] * point.x() + self.rows[1][1] * point.y() + self.rows[1][2] * point.z() + self.rows[1][3],
+            self.rows[2][0] * point.x() + self.rows[2][1] * point.y() + self.rows[2][2] * point.z() + self.rows[2][3],
+        )
+    }
+
+    /// Transforms a direction vector, ignoring translation (implicit w = 0).
+    pub fn transform_vector(&self, vector: Vec3) -> Vec3 {
+        Vec3::new(
+            self.rows[0][0] * vector.x() + self.rows[0][1] * vector.y() + self.rows[0][2] * vector.z(),
+            self.rows[1][0] * vector.x() + self.rows[1][1] * vector.y() + self.rows[1][2] * vector.z(),
+            self.rows[2][0] * vector.x() + self.rows[2][1] * vector.y() + self.rows[2][2] * vector.z(),
+        )
+    }
+
+    /// Transforms a packed batch of points, applying translation (implicit w = 1).
+    pub fn transform_packed_point<const N: usize>(&self, points: &PackedVec3<N>) -> PackedVec3<N>
+    where LaneCount<N>: SupportedLaneCount
+    {
+        PackedVec3::from_simd(
+            points.x() * Simd::splat(self.rows[0][0]) + points.y() * Simd::splat(self.rows[0][1]) + points.z() * Simd::splat(self.rows[0][2]) + Simd::splat(self.rows[0][3]),
+            points.x() * Simd::splat(self.rows[1][0]) + points.y() * Simd::splat(self.rows[1][1]) + points.z() * Simd::splat(self.rows[1][2]) + Simd::splat(self.rows[1][3]),
+            points.x() * Simd::splat(self.rows[2][0]) + points.y() * Simd::splat(self.rows[2][1]) + points.z() * Simd::splat(self.rows[2][2]) + Simd::splat(self.rows[2][3]),
+        )
+    }
+
+    /// Transforms a packed batch of direction vectors, ignoring translation (implicit w = 0).
+    pub fn transform_packed_vector<const N: usize>(&self, vectors: &PackedVec3<N>) -> PackedVec3<N>
+    where LaneCount<N>: SupportedLaneCount
+    {
+        PackedVec3::from_simd(
+            vectors.x() * Simd::splat(self.rows[0][0]) + vectors.y() * Simd::splat(self.rows[0][1]) + vectors.z() * Simd::splat(self.rows[0][2]),
+            vectors.x() * Simd::splat(self.rows[1][0]) + vectors.y() * Simd::splat(self.rows[1][1]) + vectors.z() * Simd::splat(self.rows[1][2]),
+            vectors.x() * Simd::splat(self.rows[2][0]) + vectors.y() * Simd::splat(self.rows[2][1]) + vectors.z() * Simd::splat(self.rows[2][2]),
+        )
+    }
+
+    /// Determinant of the upper-left 3x3 (linear) part, ignoring translation.
+    /// Negative for transforms that mirror handedness (e.g. an odd number of
+    /// negative scale factors), which callers can use to fix up normals that
+    /// were otherwise derived assuming an orientation-preserving transform.
+    pub fn linear_determinant(&self) -> f64 {
+        let m = self.rows;
+
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    pub fn transpose(&self) -> Mat4 {
+        let mut result = [[0.0; 4]; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                result[i][j] = self.rows[j][i];
+            }
+        }
+        Mat4::new(result)
+    }
+
+    /// Closed-form adjugate-method inverse. Returns `None` for singular matrices.
+    pub fn inverse(&self) -> Option<Mat4> {
+        let m = self.rows;
+
+        let s0 = m[0][0] * m[1][1] - m[1][0] * m[0][1];
+        let s1 = m[0][0] * m[1][2] - m[1][0] * m[0][2];
+        let s2 = m[0][0] * m[1][3] - m[1][0] * m[0][3];
+        let s3 = m[0][1] * m[1][2] - m[1][1] * m[0][2];
+        let s4 = m[0][1] * m[1][3] - m[1][1] * m[0][3];
+        let s5 = m[0][2] * m[1][3] - m[1][2] * m[0][3];
+
+        let c5 = m[2][2] * m[3][3] - m[3][2] * m[2][3];
+        let c4 = m[2][1] * m[3][3] - m[3][1] * m[2][3];
+        let c3 = m[2][1] * m[3][2] - m[3][1] * m[2][2];
+        let c2 = m[2][0] * m[3][3] - m[3][0] * m[2][3];
+        let c1 = m[2][0] * m[3][2] - m[3][0] * m[2][2];
+        let c0 = m[2][0] * m[3][1] - m[3][0] * m[2][1];
+
+        let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+
+        if det.abs() < 1E-12 {
+            return None;
+        }
+
+        let inv_det = 1.0 / det;
+
+        let result = [
+            [
+                (m[1][1] * c5 - m[1][2] * c4 + m[1][3] * c3) * inv_det,
+                (-m[0][1] * c5 + m[0][2] * c4 - m[0][3] * c3) * inv_det,
+                (m[3][1] * s5 - m[3][2] * s4 + m[3][3] * s3) * inv_det,
+                (-m[2][1] * s5 + m[2][2] * s4 - m[2][3] * s3) * inv_det,
+            ],
+            [
+                (-m[1][0] * c5 + m[1][2] * c2 - m[1][3] * c1) * inv_det,
+                (m[0][0] * c5 - m[0][2] * c2 + m[0][3] * c1) * inv_det,
+                (-m[3][0] * s5 + m[3][2] * s2 - m[3][3] * s1) * inv_det,
+                (m[2][0] * s5 - m[2][2] * s2 + m[2][3] * s1) * inv_det,
+            ],
+            [
+                (m[1][0] * c4 - m[1][1] * c2 + m[1][3] * c0) * inv_det,
+                (-m[0][0] * c4 + m[0][1] * c2 - m[0][3] * c0) * inv_det,
+                (m[3][0] * s4 - m[3][1] * s2 + m[3][3] * s0) * inv_det,
+                (-m[2][0] * s4 + m[2][1] * s2 - m[2][3] * s0) * inv_det,
+            ],
+            [
+                (-m[1][0] * c3 + m[1][1] * c1 - m[1][2] * c0) * inv_det,
+                (m[0][0] * c3 - m[0][1] * c1 + m[0][2] * c0) * inv_det,
+                (-m[3][0] * s3 + m[3][1] * s1 - m[3][2] * s0) * inv_det,
+                (m[2][0] * s3 - m[2][1] * s1 + m[2][2] * s0) * inv_det,
+            ],
+        ];
+
+        Some(Mat4::new(result))
+    }
+}
+
+/// Axis-aligned bounding box, used by the BVH to cull whole subtrees of
+/// objects before testing them individually.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub struct Aabb {
+    min: Point3,
+    max: Point3,
+}
+
+impl Aabb {
+    pub fn new(min: Point3, max: Point3) -> Aabb {
+        Aabb { min, max }
+    }
+
+    /// An empty box that unions with anything to produce exactly that thing.
+    pub fn empty() -> Aabb {
+        Aabb {
+            min: Vec3::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            max: Vec3::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        }
+    }
+
+    pub fn from_points(points: &[Point3]) -> Aabb {
+        points.iter().fold(Aabb::empty(), |acc, &p| acc.union_point(p))
+    }
+
+    pub fn min(&self) -> Point3 {
+        self.min
+    }
+
+    pub fn max(&self) -> Point3 {
+        self.max
+    }
+
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb::new(
+            Vec3::new(self.min.x().min(other.min.x()), self.min.y().min(other.min.y()), self.min.z().min(other.min.z())),
+            Vec3::new(self.max.x().max(other.max.x()), self.max.y().max(other.max.y()), self.max.z().max(other.max.z())),
+        )
+    }
+
+    pub fn union_point(&self, point: Point3) -> Aabb {
+        Aabb::new(
+            Vec3::new(self.min.x().min(point.x()), self.min.y().min(point.y()), self.min.z().min(point.z())),
+            Vec3::new(self.max.x().max(point.x()), self.max.y().max(point.y()), self.max.z().max(point.z())),
+        )
+    }
+
+    pub fn centroid(&self) -> Point3 {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Returns the `(min, max)` extent of the box along the given axis (0 = x, 1 = y, 2 = z).
+    pub fn axis(&self, axis: usize) -> (f64, f64) {
+        match axis {
+            0 => (self.min.x(), self.max.x()),
+            1 => (self.min.y(), self.max.y()),
+            2 => (self.min.z(), self.max.z()),
+            _ => panic!("axis must be 0, 1, or 2"),
+        }
+    }
+
+    /// Half the box's total face area, i.e. `sum(extent[a] * extent[b])` over
+    /// axis pairs -- used by the BVH's surface-area-heuristic split cost.
+    pub fn surface_area(&self) -> f64 {
+        let extent = self.max - self.min;
+        extent.x() * extent.y() + extent.y() * extent.z() + extent.z() * extent.x()
+    }
+
+    pub fn longest_axis(&self) -> usize {
+        let extent = self.max - self.min;
+        if extent.x() > extent.y() && extent.x() > extent.z() {
+            0
+        } else if extent.y() > extent.z() {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Scalar slab test: marches each axis narrowing `[t_min, t_max]`, rejecting
+    /// as soon as the interval collapses.
+    pub fn hit_scalar(&self, origin: Point3, direction: Vec3, t_range: &std::ops::Range<f64>) -> bool {
+        let mut t_min = t_range.start;
+        let mut t_max = t_range.end;
+
+        for axis in 0..3 {
+            let (min, max) = self.axis(axis);
+            let (origin_axis, direction_axis) = match axis {
+                0 => (origin.x(), direction.x()),
+                1 => (origin.y(), direction.y()),
+                _ => (origin.z(), direction.z()),
+            };
+
+            let inv_dir = 1.0 / direction_axis;
+            let t0 = (min - origin_axis) * inv_dir;
+            let t1 = (max - origin_axis) * inv_dir;
+
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+
+            if t_max < t_min {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Packed slab test vectorized across lanes: for each axis compute
+    /// `t0 = (min - origin) * inv_dir` and `t1 = (max - origin) * inv_dir`, narrow
+    /// `tmin`/`tmax` per axis, and a lane hits the box when `tmax >= tmin`.
+    pub fn hit_packed<const N: usize>(&self, origins: &PackedVec3<N>, directions: &PackedVec3<N>, t_range: &std::ops::Range<f64>) -> Mask<<f64 as SimdElement>::Mask, N>
+    where LaneCount<N>: SupportedLaneCount
+    {
+        let mut t_min = Simd::splat(t_range.start);
+        let mut t_max = Simd::splat(t_range.end);
+
+        let box_min = [self.min.x(), self.min.y(), self.min.z()];
+        let box_max = [self.max.x(), self.max.y(), self.max.z()];
+        let origin_axes = [origins.x(), origins.y(), origins.z()];
+        let direction_axes = [directions.x(), directions.y(), directions.z()];
+
+        for axis in 0..3 {
+            let inv_dir = Simd::splat(1.0) / direction_axes[axis];
+            let t0 = (Simd::splat(box_min[axis]) - origin_axes[axis]) * inv_dir;
+            let t1 = (Simd::splat(box_max[axis]) - origin_axes[axis]) * inv_dir;
+
+            t_min = t_min.simd_max(t0.simd_min(t1));
+            t_max = t_max.simd_min(t0.simd_max(t1));
+        }
+
+        t_max.simd_ge(t_min)
+    }
+}
+
 #[derive(Debug)]
 #[derive(Clone, Copy)]
 #[derive(Default)]
@@ -430,6 +805,77 @@ impl <const N: usize> PackedVec3<N>
 where LaneCount<N>: SupportedLaneCount
 {
     
+    #[inline]
+    pub fn ones() -> PackedVec3<N> {
+        PackedVec3 {
+            x: Simd::splat(1.0),
+            y: Simd::splat(1.0),
+            z: Simd::splat(1.0),
+        }
+    }
+
+    #[inline]
+    pub fn x_axis() -> PackedVec3<N> {
+        PackedVec3 { x: Simd::splat(1.0), y: Simd::splat(0.0), z: Simd::splat(0.0) }
+    }
+
+    #[inline]
+    pub fn y_axis() -> PackedVec3<N> {
+        PackedVec3 { x: Simd::splat(0.0), y: Simd::splat(1.0), z: Simd::splat(0.0) }
+    }
+
+    #[inline]
+    pub fn z_axis() -> PackedVec3<N> {
+        PackedVec3 { x: Simd::splat(0.0), y: Simd::splat(0.0), z: Simd::splat(1.0) }
+    }
+
+    #[inline]
+    pub fn min(&self, other: &PackedVec3<N>) -> PackedVec3<N> {
+        PackedVec3 {
+            x: self.x.simd_min(other.x),
+            y: self.y.simd_min(other.y),
+            z: self.z.simd_min(other.z),
+        }
+    }
+
+    #[inline]
+    pub fn max(&self, other: &PackedVec3<N>) -> PackedVec3<N> {
+        PackedVec3 {
+            x: self.x.simd_max(other.x),
+            y: self.y.simd_max(other.y),
+            z: self.z.simd_max(other.z),
+        }
+    }
+
+    #[inline]
+    pub fn clamp(&self, min: &PackedVec3<N>, max: &PackedVec3<N>) -> PackedVec3<N> {
+        self.max(min).min(max)
+    }
+
+    #[inline]
+    pub fn lerp(&self, other: &PackedVec3<N>, t: Simd<f64, N>) -> PackedVec3<N> {
+        *self + (*other - *self) * t
+    }
+
+    #[inline]
+    pub fn abs(&self) -> PackedVec3<N> {
+        PackedVec3 {
+            x: self.x.abs(),
+            y: self.y.abs(),
+            z: self.z.abs(),
+        }
+    }
+
+    #[inline]
+    pub fn min_element(&self) -> Simd<f64, N> {
+        self.x.simd_min(self.y).simd_min(self.z)
+    }
+
+    #[inline]
+    pub fn max_element(&self) -> Simd<f64, N> {
+        self.x.simd_max(self.y).simd_max(self.z)
+    }
+
     #[inline]
     pub fn length_squared(&self) -> Simd<f64, N> {
         self.z.mul_add(self.z , self.y.mul_add(self.y, self.x * self.x))
@@ -445,6 +891,74 @@ where LaneCount<N>: SupportedLaneCount
         *self / self.length()
     }
 
+    #[inline]
+    pub fn reflect(&self, normal: &PackedVec3<N>) -> PackedVec3<N> {
+        *self - *normal * (Simd::splat(2.0) * self.dot(normal))
+    }
+
+    #[inline]
+    pub fn refract(&self, normal: &PackedVec3<N>, refraction_ratio: Simd<f64, N>) -> PackedVec3<N> {
+        let cos_theta = (-*self).dot(normal).simd_min(Simd::splat(1.0));
+        let r_out_perpendicular = (*self + *normal * cos_theta) * refraction_ratio;
+        let r_out_parallel = *normal * -((Simd::splat(1.0) - r_out_perpendicular.length_squared()).abs().sqrt());
+        r_out_perpendicular + r_out_parallel
+    }
+
+    #[inline]
+    pub fn near_zero(&self) -> Mask<<f64 as SimdElement>::Mask, N> {
+        let epsilon = Simd::splat(1E-8);
+        self.x.abs().simd_lt(epsilon) & self.y.abs().simd_lt(epsilon) & self.z.abs().simd_lt(epsilon)
+    }
+
+    /// Draws a packet of N unit vectors uniformly distributed on the sphere, via
+    /// `z = 1 - 2u`, `phi = 2*pi*v` -- no rejection loop, so every lane advances
+    /// `rng` in lockstep unlike the scalar `Vec3::random_unit_vector`.
+    #[inline]
+    pub fn random_unit_vector(rng: &mut PackedRng<N>) -> PackedVec3<N> {
+        let u = rng.next_unit();
+        let v = rng.next_unit();
+
+        let z = Simd::splat(1.0) - Simd::splat(2.0) * u;
+        let r = (Simd::splat(1.0) - z * z).simd_max(Simd::splat(0.0)).sqrt();
+        let phi = Simd::splat(2.0 * std::f64::consts::PI) * v;
+
+        PackedVec3 {
+            x: r * simd_cos(phi),
+            y: r * simd_sin(phi),
+            z,
+        }
+    }
+
+    /// Draws a packet of N points uniformly distributed in the unit disk via
+    /// the polar method (`r = sqrt(u)`, `theta = 2*pi*v`), which needs no
+    /// rejection loop unlike the scalar `Vec3::random_in_unit_disk`.
+    #[inline]
+    pub fn random_in_unit_disk(rng: &mut PackedRng<N>) -> PackedVec3<N> {
+        let u = rng.next_unit();
+        let v = rng.next_unit();
+
+        let r = u.sqrt();
+        let theta = Simd::splat(2.0 * std::f64::consts::PI) * v;
+
+        PackedVec3 {
+            x: r * simd_cos(theta),
+            y: r * simd_sin(theta),
+            z: Simd::splat(0.0),
+        }
+    }
+
+    #[inline]
+    pub fn random_on_unit_hemisphere(rng: &mut PackedRng<N>, normal: &PackedVec3<N>) -> PackedVec3<N> {
+        let vector = Self::random_unit_vector(rng);
+        let flip = vector.dot(normal).simd_le(Simd::splat(0.0));
+
+        PackedVec3 {
+            x: flip.select(-vector.x, vector.x),
+            y: flip.select(-vector.y, vector.y),
+            z: flip.select(-vector.z, vector.z),
+        }
+    }
+
     #[inline]
     pub fn count() -> usize {
         N
@@ -462,11 +976,54 @@ where LaneCount<N>: SupportedLaneCount
         self.z[index] = value.z();
     }
 
+    /// Packs N arbitrary elements selected by `indices` out of scene-wide
+    /// SoA buffers (e.g. a BVH's flattened vertex arrays), rather than
+    /// requiring N contiguous elements like `from_vec3s`. Out-of-range
+    /// indices read as zero, matching `Simd::gather_or_default`.
+    #[inline]
+    pub fn gather(xs: &[f64], ys: &[f64], zs: &[f64], indices: Simd<usize, N>) -> PackedVec3<N> {
+        PackedVec3 {
+            x: Simd::gather_or_default(xs, indices),
+            y: Simd::gather_or_default(ys, indices),
+            z: Simd::gather_or_default(zs, indices),
+        }
+    }
+
+    /// Like `gather`, but lanes where `mask` is false (e.g. the tail of a
+    /// partial packet) are left as `0.0` instead of being gathered at all.
+    #[inline]
+    pub fn gather_select(xs: &[f64], ys: &[f64], zs: &[f64], indices: Simd<usize, N>, mask: Mask<<f64 as SimdElement>::Mask, N>) -> PackedVec3<N> {
+        let index_mask: Mask<isize, N> = mask.cast();
+        PackedVec3 {
+            x: Simd::gather_select(xs, index_mask, indices, Simd::splat(0.0)),
+            y: Simd::gather_select(ys, index_mask, indices, Simd::splat(0.0)),
+            z: Simd::gather_select(zs, index_mask, indices, Simd::splat(0.0)),
+        }
+    }
+
+    /// Writes this packet's lanes back into scene-wide SoA buffers at `indices`,
+    /// the inverse of `gather`.
+    #[inline]
+    pub fn scatter(&self, xs: &mut [f64], ys: &mut [f64], zs: &mut [f64], indices: Simd<usize, N>) {
+        self.x.scatter(xs, indices);
+        self.y.scatter(ys, indices);
+        self.z.scatter(zs, indices);
+    }
+
     #[inline]
     pub fn dot(&self, rhs: &Self) -> Simd<f64, N> {
         self.z.mul_add(rhs.z , self.y.mul_add(rhs.y, self.x * rhs.x))
     }
 
+    #[inline]
+    pub fn cross(&self, rhs: &Self) -> PackedVec3<N> {
+        PackedVec3 {
+            x: self.y.mul_add(rhs.z, -(self.z * rhs.y)),
+            y: self.z.mul_add(rhs.x, -(self.x * rhs.z)),
+            z: self.x.mul_add(rhs.y, -(self.y * rhs.x)),
+        }
+    }
+
     #[inline]
     pub fn x(&self) -> Simd<f64, N> {
         self.x
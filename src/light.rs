@@ -0,0 +1,58 @@
+use crate::color::Color;
+use crate::geometry::{Point3, Vec3};
+
+/// A light source used by the direct-lighting shader alongside the scene's objects.
+#[derive(Debug)]
+#[derive(Clone)]
+pub enum Light {
+    Point {
+        position: Point3,
+        intensity: Color,
+    },
+    Directional {
+        direction: Vec3,
+        intensity: Color,
+    },
+}
+
+impl Light {
+    /// Returns `(direction to light, distance to light, attenuated intensity)`
+    /// as seen from `location`. Directional lights have infinite distance and
+    /// no attenuation; point lights fall off with the inverse square of distance.
+    pub fn sample(&self, location: Point3) -> (Vec3, f64, Color) {
+        match self {
+            Light::Point { position, intensity } => {
+                let offset = *position - location;
+                let distance = offset.length();
+                let direction = offset / distance;
+                let attenuation = 1.0 / distance.powi(2);
+
+                (direction, distance, *intensity * attenuation)
+            },
+            Light::Directional { direction, intensity } => {
+                (-direction.unit(), f64::INFINITY, *intensity)
+            },
+        }
+    }
+
+    pub fn from_table(table: &toml::Table) -> Light {
+        let light_type = table["type"].as_str().unwrap().to_ascii_lowercase();
+        let intensity = Color::from_toml(&table["intensity"]).unwrap();
+
+        if light_type == "point" {
+            let position = Point3::from_toml(&table["position"]).unwrap();
+            Light::Point { position, intensity }
+        } else if light_type == "directional" {
+            let direction = Vec3::from_toml(&table["direction"]).unwrap();
+            Light::Directional { direction, intensity }
+        } else {
+            panic!("Unknown light type {}", light_type)
+        }
+    }
+}
+
+pub fn get_light_list(toml_light_list: &toml::value::Array) -> Vec<Light> {
+    toml_light_list.iter().map(|value| {
+        Light::from_table(value.as_table().unwrap())
+    }).collect()
+}
@@ -8,20 +8,31 @@ mod ray_tracing;
 mod materials;
 mod renderer;
 mod objects;
+mod matrix;
+mod bvh;
+mod light;
 mod toml_utils;
+mod obj_loader;
+mod filter;
+mod simd_rng;
+mod simd_width;
+mod video;
+mod quantize;
 // mod packed;
 mod ray;
 mod simd_util;
 
 use clap::{Parser, ValueEnum};
 use geometry::Vec3;
-use ray_tracing::{Camera, Scene};
+use ray_tracing::{Camera, ProjectionMode, Scene, Animation};
 use std::{sync::Arc, num::NonZeroUsize, path::{Path, PathBuf}, fs::File, io::Read};
-use renderer::TileRenderer;
+use renderer::{TileRenderer, render_passes};
 use crate::toml_utils::to_float;
 use crate::real::{Real, duration_as_secs_real};
 
-use crate::{materials::get_materials, objects::get_object_list, renderer::TileRenderMode};
+use crate::{materials::get_materials, objects::get_object_list, renderer::{TileRenderMode, detect_best_mode}, light::get_light_list};
+use crate::color::{ToneMap, TransferFunction, ColorSpace, ColorOp, ColorTransform, ChannelOptions};
+use crate::quantize::{BlockColorCount, QuantizeConfig};
 
 fn read_file_into_string (path: &Path) -> std::io::Result<String> {
     let mut file_content = String::new();
@@ -60,8 +71,9 @@ struct Cli{
     output_image: PathBuf,
 
 
-    /// Renderer mode
-    #[arg(short, long, value_enum, value_name = "Mode", default_value="vectorized")]
+    /// Renderer mode. "auto" picks the fastest kernel the running CPU supports at
+    /// startup; "scaler" pins the scalar kernel for reproducible benchmarking.
+    #[arg(short, long, value_enum, value_name = "Mode", default_value="auto")]
     render_mode: RendererMode,
 
     /// The size of tiles, sets the size tasks assign to threads when rendering.
@@ -79,6 +91,120 @@ struct Cli{
     /// Number of threads to use, default to the number of logical processors if not specified
     #[arg(short, long, value_parser= clap::value_parser!(u64).range(1..))]
     thread_count: Option<u64>,
+
+    /// Pixel reconstruction filter used to combine jittered samples within a pixel.
+    /// Only the `Scaler` render mode applies it; `Vectorized` averages samples equally.
+    #[arg(short = 'f', long, value_enum, value_name = "Filter", default_value="box")]
+    filter: FilterMode,
+
+    /// Tone-mapping operator applied to linear radiance before the output
+    /// transfer function. "clamp" matches the crate's historical behavior.
+    /// Overridden by the camera table's `tone_map` key when present.
+    #[arg(long, value_enum, value_name = "ToneMap", default_value="clamp")]
+    tone_map: ToneMapMode,
+
+    /// White point for "extended-reinhard", the input radiance that should
+    /// still map to 1.0 instead of compressing further. Ignored otherwise.
+    /// Unused when the camera table sets `tone_map`; the camera table's
+    /// `tone_map_white` key (default 4.0) is read only with
+    /// `tone_map = "extended_reinhard"`.
+    #[arg(long, value_name = "RADIANCE", default_value="4.0")]
+    tone_map_white: f64,
+
+    /// Output transfer function applied after tone mapping. "gamma" matches
+    /// the crate's historical `sqrt` curve (gamma 2.0). Overridden by the
+    /// camera table's `transfer_function` key when present.
+    #[arg(long, value_enum, value_name = "Transfer", default_value="gamma")]
+    transfer_function: TransferFunctionMode,
+
+    /// Gamma used by "gamma" transfer function. Ignored otherwise. Unused
+    /// when the camera table sets `transfer_function`; the camera table's
+    /// `gamma` key (default 2.0) is read only with `transfer_function = "gamma"`.
+    #[arg(long, value_name = "GAMMA", default_value="2.0")]
+    gamma: f64,
+
+    /// Output pixel encoding: plain RGB, or BT.709 YCbCr for downstream video
+    /// encoding. Overridden by the camera table's `color_space` key when
+    /// present.
+    #[arg(long, value_enum, value_name = "ColorSpace", default_value="rgb")]
+    color_space: ColorSpaceMode,
+
+    /// Render an animated sequence to this Y4M video file instead of a single
+    /// still image. The scene is evaluated at `frame_index / frame_count` per
+    /// frame (see `Camera::with_shutter`), so scenes with time-dependent
+    /// objects (e.g. `moving_sphere`) will animate, and a camera fly-through
+    /// can be added via an `[[animation.keyframes]]` array in the camera/
+    /// scene config (see `Animation::from_toml`). Pass `--color-space ycbcr`
+    /// too -- `Y4mSink` writes its planes as-is and expects them to already
+    /// be Y/Cb/Cr.
+    #[arg(long, value_name = "FILE")]
+    video_output: Option<PathBuf>,
+
+    /// Number of frames to render for `--video-output`. Overridden by the
+    /// `animation.frame_count` key when present.
+    #[arg(long, value_name = "FRAMES", default_value="60", value_parser= clap::value_parser!(u64).range(1..))]
+    frame_count: u64,
+
+    /// Playback frame rate written into the `--video-output` Y4M header.
+    /// Overridden by the `animation.fps` key when present.
+    #[arg(long, value_name = "FPS", default_value="24.0")]
+    fps: f64,
+
+    /// Reduce each render tile to this many representative colors (via
+    /// per-tile block vector quantization) instead of full-precision pixels.
+    /// Omit to render at full precision.
+    #[arg(long, value_enum, value_name = "Colors")]
+    block_color_count: Option<BlockColorMode>,
+
+    /// Per-tile variance (mean over the tile's pixels of the squared RGB
+    /// distance, summed over the three channels, to the tile's mean color,
+    /// on a 0-255 scale) below which a tile is emitted as a
+    /// single flat color regardless of `--block-color-count`.
+    #[arg(long, value_name = "VARIANCE", default_value="64.0")]
+    quantize_skip_threshold: f64,
+
+    /// Exposure adjustment in photographic stops, applied to linear radiance
+    /// before tone mapping and quantization. 0.0 leaves it unchanged.
+    #[arg(long, value_name = "STOPS", default_value="0.0")]
+    exposure: f64,
+
+    /// Red channel white-balance gain, applied alongside --white-balance-green/-blue.
+    #[arg(long, value_name = "GAIN", default_value="1.0")]
+    white_balance_red: f64,
+
+    /// Green channel white-balance gain, applied alongside --white-balance-red/-blue.
+    #[arg(long, value_name = "GAIN", default_value="1.0")]
+    white_balance_green: f64,
+
+    /// Blue channel white-balance gain, applied alongside --white-balance-red/-green.
+    #[arg(long, value_name = "GAIN", default_value="1.0")]
+    white_balance_blue: f64,
+
+    /// Zero out every channel but this one, e.g. to view a single AOV-style
+    /// channel in isolation. Omit to render all channels normally.
+    #[arg(long, value_enum, value_name = "Channel")]
+    isolate_channel: Option<ChannelMode>,
+
+    /// Directory to write normal/depth/ambient-occlusion AOV passes to
+    /// (as normal.png, depth.png, ao.png) alongside the usual beauty
+    /// render. Omit to skip rendering these passes entirely.
+    #[arg(long, value_name = "DIR")]
+    aov_output: Option<PathBuf>,
+
+    /// Hemisphere rays cast per sample to estimate ambient occlusion. Only
+    /// used when --aov-output is set.
+    #[arg(long, value_name = "SAMPLES", default_value="16", value_parser= clap::value_parser!(u64).range(1..))]
+    ao_samples: u64,
+
+    /// Max distance an ambient-occlusion ray travels before counting as
+    /// unoccluded. Only used when --aov-output is set.
+    #[arg(long, value_name = "DISTANCE", default_value="10.0")]
+    ao_max_distance: f64,
+
+    /// Hit distance that maps to full white in the depth AOV pass; sky
+    /// (no hit) always maps to white. Only used when --aov-output is set.
+    #[arg(long, value_name = "DISTANCE", default_value="20.0")]
+    depth_max: f64,
 }
 
 #[derive(Clone, Copy)]
@@ -91,6 +217,68 @@ enum RendererMode {
 
     // SIMD version
     Vectorized,
+
+    // pick scaler or vectorized based on detected CPU features
+    Auto,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(ValueEnum)]
+enum FilterMode {
+    Box,
+    Tent,
+    Gaussian,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(ValueEnum)]
+enum ToneMapMode {
+    Clamp,
+    Reinhard,
+    ExtendedReinhard,
+    AcesFilmic,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(ValueEnum)]
+enum TransferFunctionMode {
+    Gamma,
+    Srgb,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(ValueEnum)]
+enum ColorSpaceMode {
+    Rgb,
+    YCbCr,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(ValueEnum)]
+enum BlockColorMode {
+    One,
+    Two,
+    Four,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(ValueEnum)]
+enum ChannelMode {
+    Red,
+    Green,
+    Blue,
 }
 
 fn main() -> image::ImageResult<()> {
@@ -115,7 +303,7 @@ fn main() -> image::ImageResult<()> {
     let image_height = cli_arguments.height.try_into().unwrap();
 
     // get camera
-    let (focal_length, fov, center, look_at, up, defocus_angle) = match cli_arguments.camera {
+    let (focal_length, fov, center, look_at, up, defocus_angle, shutter_open, shutter_close, scene_projection, scene_tone_map, scene_transfer_function, scene_color_space, scene_animation, scene_frame_count, scene_fps) = match cli_arguments.camera {
         Some(camera_file_path) => {
             let camera_config_content = match read_file_into_string(&camera_file_path) {
                 Ok(c) => c,
@@ -141,8 +329,10 @@ fn main() -> image::ImageResult<()> {
 
     let camera = Arc::new(Camera::new(
         image_width, image_height,
-        focal_length, fov, 
-        center, look_at, up, defocus_angle
+        focal_length, fov,
+        center, look_at, up, defocus_angle,
+        shutter_open, shutter_close,
+        scene_projection,
     ));
 
     println!("\t Number of objects: \t {}", scene.len());
@@ -150,30 +340,140 @@ fn main() -> image::ImageResult<()> {
     let render_mode = match cli_arguments.render_mode {
         RendererMode::Scaler => TileRenderMode::Scaler,
         RendererMode::Vectorized => TileRenderMode::Vectorized,
+        RendererMode::Auto => detect_best_mode(),
     };
+    let filter = match cli_arguments.filter {
+        FilterMode::Box => filter::Filter::Box,
+        FilterMode::Tent => filter::Filter::Tent,
+        FilterMode::Gaussian => filter::Filter::Gaussian,
+    };
+    let tone_map = scene_tone_map.unwrap_or_else(|| match cli_arguments.tone_map {
+        ToneMapMode::Clamp => ToneMap::Clamp,
+        ToneMapMode::Reinhard => ToneMap::Reinhard,
+        ToneMapMode::ExtendedReinhard => ToneMap::ExtendedReinhard { white: cli_arguments.tone_map_white },
+        ToneMapMode::AcesFilmic => ToneMap::AcesFilmic,
+    });
+    let transfer_function = scene_transfer_function.unwrap_or_else(|| match cli_arguments.transfer_function {
+        TransferFunctionMode::Gamma => TransferFunction::Gamma(cli_arguments.gamma),
+        TransferFunctionMode::Srgb => TransferFunction::Srgb,
+    });
+    let color_space = scene_color_space.unwrap_or_else(|| match cli_arguments.color_space {
+        ColorSpaceMode::Rgb => ColorSpace::Rgb,
+        ColorSpaceMode::YCbCr => ColorSpace::YCbCr,
+    });
+    let quantize = cli_arguments.block_color_count.map(|mode| QuantizeConfig {
+        block_color_count: match mode {
+            BlockColorMode::One => BlockColorCount::One,
+            BlockColorMode::Two => BlockColorCount::Two,
+            BlockColorMode::Four => BlockColorCount::Four,
+        },
+        skip_threshold: cli_arguments.quantize_skip_threshold,
+    });
+
+    let mut color_ops = Vec::new();
+    if cli_arguments.exposure != 0.0 {
+        color_ops.push(ColorOp::Transform(ColorTransform::exposure(cli_arguments.exposure)));
+    }
+    if cli_arguments.white_balance_red != 1.0 || cli_arguments.white_balance_green != 1.0 || cli_arguments.white_balance_blue != 1.0 {
+        color_ops.push(ColorOp::Transform(ColorTransform::white_balance(
+            cli_arguments.white_balance_red,
+            cli_arguments.white_balance_green,
+            cli_arguments.white_balance_blue,
+        )));
+    }
+    if let Some(channel) = cli_arguments.isolate_channel {
+        color_ops.push(ColorOp::Channels(match channel {
+            ChannelMode::Red => ChannelOptions { red: true, green: false, blue: false },
+            ChannelMode::Green => ChannelOptions { red: false, green: true, blue: false },
+            ChannelMode::Blue => ChannelOptions { red: false, green: false, blue: true },
+        }));
+    }
+
     let renderer = TileRenderer::new(
         match cli_arguments.thread_count {
             Some(tc) => Some(NonZeroUsize::new(tc.try_into().unwrap()).unwrap()),
             None => None,
-        }, 
-        NonZeroUsize::new(cli_arguments.tile_size.try_into().unwrap()).unwrap(), 
-        render_mode
+        },
+        NonZeroUsize::new(cli_arguments.tile_size.try_into().unwrap()).unwrap(),
+        render_mode,
+        filter,
+        tone_map,
+        transfer_function,
+        color_space,
+        quantize,
+        color_ops,
     );
 
-    let (render_result, render_stat) = renderer.render(
-        cli_arguments.bounces.try_into().unwrap(), 
-        cli_arguments.samples_per_pixel.try_into().unwrap(),  
-        &scene, 
-        &camera
-    );
+    let frame_count = scene_frame_count.unwrap_or(cli_arguments.frame_count);
+    let fps = scene_fps.unwrap_or(cli_arguments.fps);
+
+    let render_stat = match cli_arguments.video_output {
+        Some(video_path) => {
+            let mut sink = video::Y4mSink::new(
+                File::create(video_path)?,
+                camera.image_width(),
+                camera.image_height(),
+                fps,
+            );
+
+            renderer.render_sequence(
+                cli_arguments.bounces.try_into().unwrap(),
+                cli_arguments.samples_per_pixel.try_into().unwrap(),
+                &scene,
+                &camera,
+                frame_count.try_into().unwrap(),
+                fps,
+                scene_animation.as_ref(),
+                &mut sink,
+            )?
+        },
+        None => {
+            let (render_result, render_stat) = renderer.render(
+                cli_arguments.bounces.try_into().unwrap(),
+                cli_arguments.samples_per_pixel.try_into().unwrap(),
+                &scene,
+                &camera
+            );
+
+            render_result.save(cli_arguments.output_image)?;
+
+            render_stat
+        },
+    };
 
-    render_result.save(cli_arguments.output_image)?;
+    if let Some(aov_dir) = &cli_arguments.aov_output {
+        println!("Rendering normal/depth/ao passes...");
+        std::fs::create_dir_all(aov_dir)?;
+
+        let passes = render_passes(
+            &camera,
+            &scene,
+            cli_arguments.bounces.try_into().unwrap(),
+            cli_arguments.samples_per_pixel.try_into().unwrap(),
+            cli_arguments.ao_samples.try_into().unwrap(),
+            cli_arguments.ao_max_distance,
+            cli_arguments.depth_max,
+        );
+
+        passes.normal.save(aov_dir.join("normal.png"))?;
+        passes.depth.save(aov_dir.join("depth.png"))?;
+        passes.ao.save(aov_dir.join("ao.png"))?;
+    }
 
     println!("Image Size: {} x {}", camera.image_width(), camera.image_height());
     println!("Total Pixels: {}", render_stat.pixels_rendered());
     println!("Time Taken: {:.3} seconds", duration_as_secs_real(&render_stat.duration()));
     println!("Average Pixel Rate: {:.2} px/s", render_stat.pixels_per_second());
 
+    if let Some(quantization_stat) = render_stat.quantization_stat() {
+        println!(
+            "Quantized Blocks: {} ({:?}), Flat Blocks: {}",
+            quantization_stat.blocks_quantized(),
+            quantization_stat.block_color_count(),
+            quantization_stat.blocks_flat(),
+        );
+    }
+
     match cli_arguments.report {
         Some(report_path) => {
             let mut report = toml::value::Table::new();
@@ -217,12 +517,17 @@ fn load_scene(table: &toml::value::Table) -> Arc<Scene>{
 
     let objects = get_object_list(objects_toml_array, &materials_table);
 
+    let lights = match table.get("lights") {
+        Some(a) => get_light_list(a.as_array().unwrap()),
+        None => Vec::new(),
+    };
+
     Arc::new(
-        Scene::from_list(&objects)
+        Scene::from_list_with_lights(&objects, &lights)
     )
 }
 
-fn load_camera(table: &toml::value::Table) -> Option<(Real, Real, Vec3, Vec3, Vec3, Real)> {
+fn load_camera(table: &toml::value::Table) -> Option<(Real, Real, Vec3, Vec3, Vec3, Real, Real, Real, ProjectionMode, Option<ToneMap>, Option<TransferFunction>, Option<ColorSpace>, Option<Animation>, Option<u64>, Option<f64>)> {
     let camera_toml_table = match table.get("camera") {
         Some(t) => t.as_table().unwrap(),
         None => return None,
@@ -271,5 +576,53 @@ fn load_camera(table: &toml::value::Table) -> Option<(Real, Real, Vec3, Vec3, Ve
         None => 0.0,
     };
 
-    Some((focal_length, fov, center, look_at, up, defocus_angle))
+    let shutter_open = match camera_toml_table.get("shutter_open") {
+        Some(f) => to_float(f).unwrap(),
+        None => 0.0,
+    };
+
+    let shutter_close = match camera_toml_table.get("shutter_close") {
+        Some(f) => to_float(f).unwrap(),
+        None => 0.0,
+    };
+
+    let projection = match camera_toml_table.get("projection").and_then(|v| v.as_str()) {
+        Some("orthographic") => ProjectionMode::Orthographic,
+        Some("equirectangular") => ProjectionMode::Equirectangular,
+        Some("fisheye") => ProjectionMode::Fisheye,
+        _ => ProjectionMode::Perspective,
+    };
+
+    let tone_map = match camera_toml_table.get("tone_map").and_then(|v| v.as_str()) {
+        Some("clamp") => Some(ToneMap::Clamp),
+        Some("reinhard") => Some(ToneMap::Reinhard),
+        Some("extended_reinhard") => {
+            let white = camera_toml_table.get("tone_map_white").and_then(|v| to_float(v)).unwrap_or(4.0);
+            Some(ToneMap::ExtendedReinhard { white })
+        },
+        Some("aces_filmic") => Some(ToneMap::AcesFilmic),
+        _ => None,
+    };
+
+    let transfer_function = match camera_toml_table.get("transfer_function").and_then(|v| v.as_str()) {
+        Some("gamma") => {
+            let gamma = camera_toml_table.get("gamma").and_then(|v| to_float(v)).unwrap_or(2.0);
+            Some(TransferFunction::Gamma(gamma))
+        },
+        Some("srgb") => Some(TransferFunction::Srgb),
+        _ => None,
+    };
+
+    let color_space = match camera_toml_table.get("color_space").and_then(|v| v.as_str()) {
+        Some("rgb") => Some(ColorSpace::Rgb),
+        Some("ycbcr") => Some(ColorSpace::YCbCr),
+        _ => None,
+    };
+
+    let animation = Animation::from_toml(table);
+    let animation_table = table.get("animation").and_then(|v| v.as_table());
+    let animation_frame_count = animation_table.and_then(|t| t.get("frame_count")).and_then(|v| v.as_integer()).map(|v| v as u64);
+    let animation_fps = animation_table.and_then(|t| t.get("fps")).and_then(|v| to_float(v));
+
+    Some((focal_length, fov, center, look_at, up, defocus_angle, shutter_open, shutter_close, projection, tone_map, transfer_function, color_space, animation, animation_frame_count, animation_fps))
 }
\ No newline at end of file
@@ -27,6 +27,10 @@ pub fn load_material_from_toml(table: &toml::Table) -> Arc<dyn Material> {
         Metal::from_table(table)
     } else if material_type == "dielectric" {
         Dielectric::from_table(table)
+    } else if material_type == "image_texture" {
+        ImageTexture::from_table(table)
+    } else if material_type == "light" {
+        DiffuseLight::from_table(table)
     } else {
         panic!("Unknown material type {}!", material_type)
     }
@@ -36,6 +40,23 @@ pub trait Material : Debug + Sync + Send {
     fn get_hit_result(&self, ray: &Ray, hit_record: &HitRecord) -> HitResult;
 
     fn from_table(table: &toml::Table) -> Arc<dyn Material> where Self: Sized;
+
+    /// Base color used by the direct-lighting shader's diffuse and specular
+    /// terms; materials without a natural albedo (e.g. `Dielectric`) just stay white.
+    fn diffuse_color(&self) -> Color {
+        Color::white()
+    }
+
+    /// Blinn-Phong specular exponent used by the direct-lighting shader.
+    fn shininess(&self) -> f64 {
+        32.0
+    }
+
+    /// Light emitted by this material at a hit, independent of any scattered
+    /// ray. Almost nothing glows, so the default is no emission at all.
+    fn emitted(&self, _ray: &Ray, _hit_record: &HitRecord) -> Color {
+        Color::black()
+    }
 }
 
 #[derive(Debug)]
@@ -51,7 +72,7 @@ impl Lambertian {
 }
 
 impl Material for Lambertian {
-    fn get_hit_result(&self, _ray: &Ray, hit_record: &HitRecord) -> HitResult {
+    fn get_hit_result(&self, ray: &Ray, hit_record: &HitRecord) -> HitResult {
 
         let mut scatter_direction = Vec3::random_unit_vector() + hit_record.normal();
 
@@ -59,13 +80,17 @@ impl Material for Lambertian {
             scatter_direction = hit_record.normal();
         }
 
-        HitResult::new_scattered(self.albedo, Ray::new(hit_record.location(), scatter_direction))
+        HitResult::new_scattered(self.albedo, Ray::new_with_time(hit_record.location(), scatter_direction, ray.time()))
     }
 
     fn from_table(table: &toml::Table) -> Arc<dyn Material> where Self: Sized {
         let albedo = Color::from_toml(&table["albedo"]).unwrap();
         Arc::new(Lambertian::new(albedo))
     }
+
+    fn diffuse_color(&self) -> Color {
+        self.albedo
+    }
 }
 
 #[derive(Debug)]
@@ -91,7 +116,7 @@ impl Metal {
 impl Material for Metal {
     fn get_hit_result(&self, ray: &Ray, hit_record: &HitRecord) -> HitResult {
         let reflected = ray.direction().reflect(&hit_record.normal()) + self.fuzzy_factor * Vec3::random_unit_vector();
-        let scattered = Ray::new(hit_record.location(), reflected);
+        let scattered = Ray::new_with_time(hit_record.location(), reflected, ray.time());
 
         HitResult::new_scattered(self.albedo, scattered)
     }
@@ -104,6 +129,10 @@ impl Material for Metal {
 
         Arc::new(Metal::new(albedo, fuzzy_factor))
     }
+
+    fn diffuse_color(&self) -> Color {
+        self.albedo
+    }
 }
 
 #[derive(Debug)]
@@ -111,11 +140,18 @@ impl Material for Metal {
 pub struct Dielectric {
     index_of_refraction: f64,
     hollow: bool,
+    absorption: Color,
 }
 
 impl Dielectric {
     pub fn new(index_of_refraction: f64, hollow: bool) -> Dielectric {
-        Dielectric { index_of_refraction, hollow}
+        Dielectric { index_of_refraction, hollow, absorption: Color::black() }
+    }
+
+    /// `absorption` is a per-channel Beer-Lambert coefficient applied to the
+    /// path traveled inside the medium, for tinted glass and gems.
+    pub fn with_absorption(index_of_refraction: f64, hollow: bool, absorption: Color) -> Dielectric {
+        Dielectric { index_of_refraction, hollow, absorption }
     }
 
     fn reflectance(cosine: f64, index_of_refraction: f64) -> f64 {
@@ -139,18 +175,184 @@ impl Material for Dielectric {
         } else {
             ray.direction().refract(&normal, refraction_ratio)
         };
-        
+
+        // A hit on the interior wall (front_face == false) means the incoming
+        // ray just traveled the full interior path segment since it entered
+        // the medium, so tint the carried color by Beer-Lambert for that
+        // distance. Entry hits (front_face == true) carry no interior path yet.
+        let transmission = if hit_record.front_face() {
+            Color::white()
+        } else {
+            (self.absorption * -hit_record.t()).exp()
+        };
+
         HitResult::new_scattered(
-            Color::white(), 
-            Ray::new(hit_record.location(), refracted_direction),
+            transmission,
+            Ray::new_with_time(hit_record.location(), refracted_direction, ray.time()),
         )
     }
 
     fn from_table(table: &toml::Table) -> Arc<dyn Material> where Self: Sized {
         let index_of_refraction = to_float(&table["index_of_refraction"]).unwrap();
         let hollow = table["hollow"].as_bool().unwrap();
+        let absorption = table.get("attenuation")
+            .or_else(|| table.get("tint"))
+            .and_then(Color::from_toml)
+            .unwrap_or(Color::black());
+
+        Arc::new(Dielectric::with_absorption(index_of_refraction, hollow, absorption))
+    }
+}
+
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub enum TextureSamplingMode {
+    Nearest,
+    Bilinear,
+}
+
+/// Diffuse material whose albedo is sampled from an image using the hit's
+/// `(u, v)` surface coordinates instead of being a constant `Color`.
+#[derive(Debug)]
+pub struct ImageTexture {
+    image: image::RgbImage,
+    sampling: TextureSamplingMode,
+}
+
+impl ImageTexture {
+    pub fn new(image: image::RgbImage, sampling: TextureSamplingMode) -> ImageTexture {
+        ImageTexture { image, sampling }
+    }
+
+    fn pixel_color(&self, x: u32, y: u32) -> Color {
+        let pixel = self.image.get_pixel(x, y);
+        Color::new(pixel[0] as f64 / 255.0, pixel[1] as f64 / 255.0, pixel[2] as f64 / 255.0)
+    }
+
+    fn sample(&self, u: f64, v: f64) -> Color {
+        let (width, height) = self.image.dimensions();
+        let x = u.rem_euclid(1.0) * (width as f64);
+        let y = (1.0 - v.rem_euclid(1.0)) * (height as f64);
+
+        match self.sampling {
+            TextureSamplingMode::Nearest => {
+                let px = (x as u32).min(width - 1);
+                let py = (y as u32).min(height - 1);
+                self.pixel_color(px, py)
+            },
+            TextureSamplingMode::Bilinear => {
+                let x0 = x.floor();
+                let y0 = y.floor();
+                let fraction_x = x - x0;
+                let fraction_y = y - y0;
+
+                let x0 = (x0 as u32).min(width - 1);
+                let y0 = (y0 as u32).min(height - 1);
+                let x1 = (x0 + 1).min(width - 1);
+                let y1 = (y0 + 1).min(height - 1);
+
+                let top = self.pixel_color(x0, y0) * (1.0 - fraction_x) + self.pixel_color(x1, y0) * fraction_x;
+                let bottom = self.pixel_color(x0, y1) * (1.0 - fraction_x) + self.pixel_color(x1, y1) * fraction_x;
+
+                top * (1.0 - fraction_y) + bottom * fraction_y
+            },
+        }
+    }
+}
+
+impl Material for ImageTexture {
+    fn get_hit_result(&self, ray: &Ray, hit_record: &HitRecord) -> HitResult {
+        let mut scatter_direction = Vec3::random_unit_vector() + hit_record.normal();
+
+        if scatter_direction.near_zero() {
+            scatter_direction = hit_record.normal();
+        }
+
+        let albedo = self.sample(hit_record.u(), hit_record.v());
+
+        HitResult::new_scattered(albedo, Ray::new_with_time(hit_record.location(), scatter_direction, ray.time()))
+    }
+
+    fn from_table(table: &toml::Table) -> Arc<dyn Material> where Self: Sized {
+        let filename = table["filename"].as_str().unwrap();
+        let image = image::open(filename).unwrap().to_rgb8();
+
+        let sampling = match table.get("sampling").and_then(|v| v.as_str()) {
+            Some("nearest") => TextureSamplingMode::Nearest,
+            Some("bilinear") | None => TextureSamplingMode::Bilinear,
+            Some(other) => panic!("Unknown texture sampling mode {}", other),
+        };
+
+        Arc::new(ImageTexture::new(image, sampling))
+    }
+}
+
+/// Emissive material for light sources: it never scatters, it only radiates
+/// its `emit` color, so a ray that hits one terminates there.
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct DiffuseLight {
+    emit: Color,
+}
+
+impl DiffuseLight {
+    pub fn new(emit: Color) -> Self {
+        Self { emit }
+    }
+}
+
+impl Material for DiffuseLight {
+    fn get_hit_result(&self, _ray: &Ray, _hit_record: &HitRecord) -> HitResult {
+        HitResult::new_emitted(self.emit)
+    }
+
+    fn from_table(table: &toml::Table) -> Arc<dyn Material> where Self: Sized {
+        let emit = Color::from_toml(&table["emit"]).unwrap();
+        Arc::new(DiffuseLight::new(emit))
+    }
+
+    fn emitted(&self, _ray: &Ray, _hit_record: &HitRecord) -> Color {
+        self.emit
+    }
+}
 
-        Arc::new(Dielectric::new(index_of_refraction, hollow))
+/// Parameters pulled from one Wavefront `.mtl` `newmtl` block, kept in raw
+/// form (rather than as a `Material`) so `material_from_mtl` alone decides
+/// which of the existing material types a statement combination maps to.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MtlMaterial {
+    pub kd: Option<(f64, f64, f64)>,
+    pub ks: Option<(f64, f64, f64)>,
+    pub ke: Option<(f64, f64, f64)>,
+    pub ns: Option<f64>,
+    pub ni: Option<f64>,
+    pub d: Option<f64>,
+}
+
+/// Maps a parsed MTL material onto the existing material types: a non-black
+/// `Ke` becomes a `DiffuseLight`; `Ni` with `d < 1` (transparent) becomes a
+/// `Dielectric`; a high `Ns` with `Ks` becomes a `Metal` with its fuzziness
+/// derived from `Ns`; everything else falls back to a `Lambertian` on `Kd`.
+pub fn material_from_mtl(mtl: &MtlMaterial) -> Arc<dyn Material> {
+    if let Some(ke) = mtl.ke {
+        if ke != (0.0, 0.0, 0.0) {
+            return Arc::new(DiffuseLight::new(Color::new(ke.0, ke.1, ke.2)));
+        }
+    }
+
+    if let (Some(ni), Some(d)) = (mtl.ni, mtl.d) {
+        if d < 1.0 {
+            return Arc::new(Dielectric::new(ni, false));
+        }
     }
+
+    if let (Some(ns), Some((r, g, b))) = (mtl.ns, mtl.ks) {
+        if ns > 200.0 {
+            let fuzzy_factor = 1.0 - (ns / 1000.0).clamp(0.0, 1.0);
+            return Arc::new(Metal::new(Color::new(r, g, b), fuzzy_factor));
+        }
+    }
+
+    let (r, g, b) = mtl.kd.unwrap_or((1.0, 1.0, 1.0));
+    Arc::new(Lambertian::new(Color::new(r, g, b)))
 }
-    
\ No newline at end of file
@@ -0,0 +1,177 @@
+use std::simd::{Simd, Mask, LaneCount, SupportedLaneCount, SimdElement, StdFloat, cmp::SimdPartialOrd};
+
+use crate::geometry::{Vec3, PackedVec3, PackedPoint3, Mat4};
+
+/// `N` independent 4x4 matrices, stored row-major with one SIMD lane per matrix.
+/// Lets a whole batch of per-object transforms (e.g. instance `M_inv`/`M_invT`)
+/// be built and inverted in one vectorized pass instead of N scalar ones.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub struct Matrix4<const N: usize>
+where LaneCount<N>: SupportedLaneCount
+{
+    rows: [[Simd<f64, N>; 4]; 4],
+}
+
+impl <const N: usize> Matrix4<N>
+where LaneCount<N>: SupportedLaneCount
+{
+    pub fn from_mat4s(matrices: &[Mat4; N]) -> Matrix4<N> {
+        let mut rows = [[Simd::splat(0.0); 4]; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                rows[i][j] = Simd::from_array(std::array::from_fn(|lane| matrices[lane].element(i, j)));
+            }
+        }
+        Matrix4 { rows }
+    }
+
+    pub fn to_mat4s(&self) -> [Mat4; N] {
+        std::array::from_fn(|lane| {
+            let mut mat_rows = [[0.0; 4]; 4];
+            for i in 0..4 {
+                for j in 0..4 {
+                    mat_rows[i][j] = self.rows[i][j][lane];
+                }
+            }
+            Mat4::new(mat_rows)
+        })
+    }
+
+    /// Broadcasts a single scalar matrix to every lane.
+    pub fn splat(matrix: Mat4) -> Matrix4<N> {
+        let mut rows = [[Simd::splat(0.0); 4]; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                rows[i][j] = Simd::splat(matrix.element(i, j));
+            }
+        }
+        Matrix4 { rows }
+    }
+
+    pub fn identity() -> Matrix4<N> {
+        Matrix4::splat(Mat4::identity())
+    }
+
+    pub fn translation(offset: Vec3) -> Matrix4<N> {
+        Matrix4::splat(Mat4::translation(offset))
+    }
+
+    pub fn scaling(factor: Vec3) -> Matrix4<N> {
+        Matrix4::splat(Mat4::scaling(factor))
+    }
+
+    pub fn rotation_x(angle_radians: f64) -> Matrix4<N> {
+        Matrix4::splat(Mat4::rotation_x(angle_radians))
+    }
+
+    pub fn rotation_y(angle_radians: f64) -> Matrix4<N> {
+        Matrix4::splat(Mat4::rotation_y(angle_radians))
+    }
+
+    pub fn rotation_z(angle_radians: f64) -> Matrix4<N> {
+        Matrix4::splat(Mat4::rotation_z(angle_radians))
+    }
+
+    /// Per-lane matrix multiplication: lane `i`'s output is `self`'s lane-`i`
+    /// matrix times `rhs`'s lane-`i` matrix.
+    pub fn mul(&self, rhs: &Matrix4<N>) -> Matrix4<N> {
+        let mut rows = [[Simd::splat(0.0); 4]; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                let mut sum = Simd::splat(0.0);
+                for k in 0..4 {
+                    sum += self.rows[i][k] * rhs.rows[k][j];
+                }
+                rows[i][j] = sum;
+            }
+        }
+        Matrix4 { rows }
+    }
+
+    pub fn transpose(&self) -> Matrix4<N> {
+        let mut rows = [[Simd::splat(0.0); 4]; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                rows[i][j] = self.rows[j][i];
+            }
+        }
+        Matrix4 { rows }
+    }
+
+    /// Shared homogeneous-transform helper: `w = 1.0` for points, `w = 0.0` for
+    /// direction vectors. Lane `i`'s matrix is applied to lane `i`'s `(x,y,z,w)`.
+    pub fn transform_packed(&self, xyz: &PackedVec3<N>, w: Simd<f64, N>) -> PackedVec3<N> {
+        PackedVec3::from_simd(
+            xyz.x() * self.rows[0][0] + xyz.y() * self.rows[0][1] + xyz.z() * self.rows[0][2] + w * self.rows[0][3],
+            xyz.x() * self.rows[1][0] + xyz.y() * self.rows[1][1] + xyz.z() * self.rows[1][2] + w * self.rows[1][3],
+            xyz.x() * self.rows[2][0] + xyz.y() * self.rows[2][1] + xyz.z() * self.rows[2][2] + w * self.rows[2][3],
+        )
+    }
+
+    pub fn transform_point(&self, points: &PackedPoint3<N>) -> PackedPoint3<N> {
+        self.transform_packed(points, Simd::splat(1.0))
+    }
+
+    pub fn transform_vector(&self, vectors: &PackedVec3<N>) -> PackedVec3<N> {
+        self.transform_packed(vectors, Simd::splat(0.0))
+    }
+
+    /// Closed-form adjugate-method inverse, computed for all `N` lanes at once:
+    /// 2x2 minor products build the cofactor matrix, the determinant is the dot
+    /// of the first row with its cofactor row, and the adjugate is scaled by
+    /// `1/det`. Lanes whose `|det| < eps` are marked in the returned mask so
+    /// callers can skip (rather than divide by) singular matrices.
+    pub fn inverse(&self, eps: f64) -> (Matrix4<N>, Mask<<f64 as SimdElement>::Mask, N>) {
+        let m = &self.rows;
+
+        let s0 = m[0][0] * m[1][1] - m[1][0] * m[0][1];
+        let s1 = m[0][0] * m[1][2] - m[1][0] * m[0][2];
+        let s2 = m[0][0] * m[1][3] - m[1][0] * m[0][3];
+        let s3 = m[0][1] * m[1][2] - m[1][1] * m[0][2];
+        let s4 = m[0][1] * m[1][3] - m[1][1] * m[0][3];
+        let s5 = m[0][2] * m[1][3] - m[1][2] * m[0][3];
+
+        let c5 = m[2][2] * m[3][3] - m[3][2] * m[2][3];
+        let c4 = m[2][1] * m[3][3] - m[3][1] * m[2][3];
+        let c3 = m[2][1] * m[3][2] - m[3][1] * m[2][2];
+        let c2 = m[2][0] * m[3][3] - m[3][0] * m[2][3];
+        let c1 = m[2][0] * m[3][2] - m[3][0] * m[2][2];
+        let c0 = m[2][0] * m[3][1] - m[3][0] * m[2][1];
+
+        let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+
+        let singular = det.abs().simd_lt(Simd::splat(eps));
+        let safe_det = singular.select(Simd::splat(1.0), det);
+        let inv_det = Simd::splat(1.0) / safe_det;
+
+        let rows = [
+            [
+                (m[1][1] * c5 - m[1][2] * c4 + m[1][3] * c3) * inv_det,
+                (-m[0][1] * c5 + m[0][2] * c4 - m[0][3] * c3) * inv_det,
+                (m[3][1] * s5 - m[3][2] * s4 + m[3][3] * s3) * inv_det,
+                (-m[2][1] * s5 + m[2][2] * s4 - m[2][3] * s3) * inv_det,
+            ],
+            [
+                (-m[1][0] * c5 + m[1][2] * c2 - m[1][3] * c1) * inv_det,
+                (m[0][0] * c5 - m[0][2] * c2 + m[0][3] * c1) * inv_det,
+                (-m[3][0] * s5 + m[3][2] * s2 - m[3][3] * s1) * inv_det,
+                (m[2][0] * s5 - m[2][2] * s2 + m[2][3] * s1) * inv_det,
+            ],
+            [
+                (m[1][0] * c4 - m[1][1] * c2 + m[1][3] * c0) * inv_det,
+                (-m[0][0] * c4 + m[0][1] * c2 - m[0][3] * c0) * inv_det,
+                (m[3][0] * s4 - m[3][1] * s2 + m[3][3] * s0) * inv_det,
+                (-m[2][0] * s4 + m[2][1] * s2 - m[2][3] * s0) * inv_det,
+            ],
+            [
+                (-m[1][0] * c3 + m[1][1] * c1 - m[1][2] * c0) * inv_det,
+                (m[0][0] * c3 - m[0][1] * c1 + m[0][2] * c0) * inv_det,
+                (-m[3][0] * s3 + m[3][1] * s1 - m[3][2] * s0) * inv_det,
+                (m[2][0] * s3 - m[2][1] * s1 + m[2][2] * s0) * inv_det,
+            ],
+        ];
+
+        (Matrix4 { rows }, singular)
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::color::Color;
+use crate::geometry::Point3;
+use crate::materials::{material_from_mtl, Lambertian, Material, MtlMaterial};
+use crate::objects::{Mesh, Triangle};
+
+/// Loads a Wavefront `.obj` file (plus its `mtllib`-referenced `.mtl`, if
+/// any) into a `Mesh`, so scenes authored in Blender can be dropped in next
+/// to the hand-written TOML primitives.
+pub fn load_obj_mesh(obj_path: &Path) -> Mesh {
+    let obj_dir = obj_path.parent().unwrap_or_else(|| Path::new("."));
+    let content = fs::read_to_string(obj_path)
+        .unwrap_or_else(|why| panic!("Can't read obj file {}: {}", obj_path.display(), why));
+
+    let default_material: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.8, 0.8, 0.8)));
+
+    let mut vertices: Vec<Point3> = Vec::new();
+    let mut materials: HashMap<String, Arc<dyn Material>> = HashMap::new();
+    let mut current_material = default_material.clone();
+    let mut triangles: Vec<Triangle> = Vec::new();
+
+    for line in content.lines() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                let x: f64 = tokens.next().unwrap().parse().unwrap();
+                let y: f64 = tokens.next().unwrap().parse().unwrap();
+                let z: f64 = tokens.next().unwrap().parse().unwrap();
+                vertices.push(Point3::new(x, y, z));
+            },
+            Some("mtllib") => {
+                let mtl_name = tokens.next().unwrap();
+                materials = load_mtl_materials(&obj_dir.join(mtl_name));
+            },
+            Some("usemtl") => {
+                let material_name = tokens.next().unwrap();
+                current_material = materials.get(material_name).cloned().unwrap_or_else(|| default_material.clone());
+            },
+            Some("f") => {
+                let indices: Vec<usize> = tokens.map(|token| parse_face_vertex_index(token, vertices.len())).collect();
+                assert!(indices.len() >= 3, "Face with fewer than 3 vertices");
+
+                // fan-triangulate faces with more than 3 vertices
+                for i in 1..(indices.len() - 1) {
+                    triangles.push(Triangle::new(
+                        vertices[indices[0]],
+                        vertices[indices[i]],
+                        vertices[indices[i + 1]],
+                        &current_material,
+                    ));
+                }
+            },
+            _ => (),
+        }
+    }
+
+    Mesh::new(triangles)
+}
+
+/// Resolves an OBJ face-vertex token (`v`, `v/vt`, or `v/vt/vn`) to a
+/// zero-based index, handling OBJ's 1-based and negative (relative to the
+/// end of the vertex list so far) indexing.
+fn parse_face_vertex_index(token: &str, vertex_count: usize) -> usize {
+    let v_index: i64 = token.split('/').next().unwrap().parse().unwrap();
+
+    if v_index > 0 {
+        (v_index - 1) as usize
+    } else {
+        (vertex_count as i64 + v_index) as usize
+    }
+}
+
+fn load_mtl_materials(mtl_path: &Path) -> HashMap<String, Arc<dyn Material>> {
+    let content = fs::read_to_string(mtl_path)
+        .unwrap_or_else(|why| panic!("Can't read mtl file {}: {}", mtl_path.display(), why));
+
+    let mut materials = HashMap::new();
+    let mut current_name: Option<String> = None;
+    let mut current = MtlMaterial::default();
+
+    for line in content.lines() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("newmtl") => {
+                if let Some(name) = current_name.take() {
+                    materials.insert(name, material_from_mtl(&current));
+                }
+                current_name = Some(tokens.next().unwrap().to_owned());
+                current = MtlMaterial::default();
+            },
+            Some("Kd") => current.kd = Some(parse_rgb(tokens)),
+            Some("Ks") => current.ks = Some(parse_rgb(tokens)),
+            Some("Ke") => current.ke = Some(parse_rgb(tokens)),
+            Some("Ns") => current.ns = tokens.next().map(|v| v.parse().unwrap()),
+            Some("Ni") => current.ni = tokens.next().map(|v| v.parse().unwrap()),
+            Some("d") => current.d = tokens.next().map(|v| v.parse().unwrap()),
+            _ => (),
+        }
+    }
+
+    if let Some(name) = current_name.take() {
+        materials.insert(name, material_from_mtl(&current));
+    }
+
+    materials
+}
+
+fn parse_rgb<'a>(mut tokens: impl Iterator<Item = &'a str>) -> (f64, f64, f64) {
+    let r = tokens.next().unwrap().parse().unwrap();
+    let g = tokens.next().unwrap().parse().unwrap();
+    let b = tokens.next().unwrap().parse().unwrap();
+    (r, g, b)
+}
@@ -1,14 +1,15 @@
-use crate::geometry::{Vec3, Point3};
+use crate::geometry::{Vec3, Point3, Mat4, Aabb};
 use crate::materials::Material;
 use crate::ray::{Ray, PackedRays};
 use crate::color::Color;
 use crate::toml_utils::to_float;
 use crate::geometry::{PackedVec3, PackedPoint3};
-use crate::simd_util::{masked_assign, simd_inside, PackedOptionalReference, negate_simd_float, masked_select};
+use crate::simd_util::{masked_assign, simd_inside, PackedOptionalReference, negate_simd_float, masked_select, simd_atan2, simd_asin};
 
 use std::collections::HashMap;
 use std::simd::cmp::SimdPartialOrd;
 use std::simd::{LaneCount, SupportedLaneCount, Simd, Mask, SimdElement, StdFloat};
+use std::simd::num::SimdFloat;
 use std::sync::Arc;
 use std::fmt::Debug;
 
@@ -16,21 +17,62 @@ use std::fmt::Debug;
 #[derive(Clone)]
 pub enum Object {
     Sphere(Sphere),
+    MovingSphere(MovingSphere),
+    Instance(Box<Instance>),
+    Triangle(Triangle),
+    Mesh(Mesh),
 }
 
 impl Object {
     pub fn hit(&self, ray: &Ray, t_range: &std::ops::Range<f64>) -> Option<HitRecord> {
         match self {
             Object::Sphere(s) => s.hit(ray, t_range),
+            Object::MovingSphere(s) => s.hit(ray, t_range),
+            Object::Instance(i) => i.hit(ray, t_range),
+            Object::Triangle(t) => t.hit(ray, t_range),
+            Object::Mesh(m) => m.hit(ray, t_range),
         }
     }
 
     // #[inline(never)]
-    pub fn hit_packed<'a, const N: usize>(&'a self, rays: &PackedRays<N>, t_range: &std::ops::Range<f64>, hit_records: &mut PackedHitRecords<'a, N>) 
+    pub fn hit_packed<'a, const N: usize>(&'a self, rays: &PackedRays<N>, t_range: &std::ops::Range<f64>, hit_records: &mut PackedHitRecords<'a, N>)
     where LaneCount<N>: SupportedLaneCount
     {
         match self {
             Object::Sphere(s) => s.hit_packed(rays, t_range, hit_records),
+            Object::MovingSphere(s) => s.hit_packed(rays, t_range, hit_records),
+            Object::Instance(i) => i.hit_packed(rays, t_range, hit_records),
+            Object::Triangle(t) => t.hit_packed(rays, t_range, hit_records),
+            Object::Mesh(m) => m.hit_packed(rays, t_range, hit_records),
+        }
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        match self {
+            Object::Sphere(s) => s.aabb(),
+            Object::MovingSphere(s) => s.aabb(),
+            Object::Instance(i) => i.aabb(),
+            Object::Triangle(t) => t.aabb(),
+            Object::Mesh(m) => m.aabb(),
+        }
+    }
+
+    /// Whether this object's outward normal is a pseudovector -- derived from
+    /// a cross product of edge vectors, like `Triangle`'s `e1 x e2` -- rather
+    /// than a true covector, like `Sphere`'s radial `location - center`
+    /// gradient. Transforming a true covector by `M` only ever needs
+    /// `M^-T`; a pseudovector additionally flips sign under an
+    /// orientation-reversing (negative-determinant) `M`, since it encodes a
+    /// handedness convention (here, the edge order `e1, e2`) rather than a
+    /// direction intrinsic to the surface. `Instance` uses this to decide
+    /// whether its handedness correction applies to a given child.
+    fn normal_is_pseudovector(&self) -> bool {
+        match self {
+            Object::Sphere(_) => false,
+            Object::MovingSphere(_) => false,
+            Object::Instance(_) => false,
+            Object::Triangle(_) => true,
+            Object::Mesh(_) => true,
         }
     }
 }
@@ -43,14 +85,31 @@ pub fn get_object_list(toml_object_list: &toml::value::Array, material_table: &H
 
 pub fn load_object_from_toml(table: &toml::Table, material_table: &HashMap<String, Arc<dyn Material>>) -> Object {
     let object_type = table["type"].as_str().unwrap().to_ascii_lowercase();
-    
+
     if object_type == "sphere" {
         Object::Sphere(Sphere::from_table(table, material_table))
+    } else if object_type == "moving_sphere" {
+        Object::MovingSphere(MovingSphere::from_table(table, material_table))
+    } else if object_type == "instance" {
+        Object::Instance(Box::new(Instance::from_table(table, material_table)))
+    } else if object_type == "triangle" {
+        Object::Triangle(Triangle::from_table(table, material_table))
+    } else if object_type == "mesh" {
+        Object::Mesh(Mesh::from_table(table, material_table))
     } else {
         panic!("Unknown object type {}", object_type)
     }
 }
 
+/// Maps a unit direction to `(u, v)` surface coordinates via the standard
+/// spherical parameterization. Exact for `Sphere`; applied uniformly since no
+/// other primitive here defines its own parameterization yet.
+fn spherical_uv(d: Vec3) -> (f64, f64) {
+    let u = 0.5 + d.z().atan2(d.x()) / (2.0 * std::f64::consts::PI);
+    let v = 0.5 - d.y().asin() / std::f64::consts::PI;
+    (u, v)
+}
+
 #[derive(Debug)]
 #[derive(Clone)]
 pub struct HitRecord<'a> {
@@ -58,26 +117,33 @@ pub struct HitRecord<'a> {
     normal: Vec3,
     t: f64,
     front_face: bool,
+    u: f64,
+    v: f64,
+    time: f64,
     material: &'a Arc<dyn Material>,
 }
 
 impl HitRecord<'_> {
     pub fn new <'a> (ray: &Ray, location: Point3, outward_normal: Vec3, t: f64, material: &'a Arc<dyn Material>) -> HitRecord<'a> {
         debug_assert!((outward_normal.length() - 1.0).abs() < 1E-9, "expecting 1.0, got {}", outward_normal.length());
-        
-        
+
+
         let (front_face, normal) = if ray.direction().dot(&outward_normal) < 0.0 {
             (true, outward_normal)
         } else {
             (false, -outward_normal)
         };
-        
+
+        let (u, v) = spherical_uv(normal);
 
         HitRecord {
             location: location,
             normal: normal,
             t: t,
             front_face: front_face,
+            u,
+            v,
+            time: ray.time(),
             material: material
         }
     }
@@ -98,11 +164,31 @@ impl HitRecord<'_> {
         self.front_face
     }
 
+    pub fn u(&self) -> f64 {
+        self.u
+    }
+
+    pub fn v(&self) -> f64 {
+        self.v
+    }
+
+    /// Point in the camera's shutter interval when the ray that produced this
+    /// hit was cast (moving objects evaluate their position from `ray.time()`).
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
     pub fn hit_result(&self, ray: &Ray) -> HitResult {
         self.material.get_hit_result(ray, self)
     }
 }
 
+impl <'a> HitRecord<'a> {
+    pub fn material(&self) -> &'a Arc<dyn Material> {
+        self.material
+    }
+}
+
 
 #[derive(Debug)]
 #[derive(Clone)]
@@ -114,11 +200,14 @@ where LaneCount<N>: SupportedLaneCount
     t: Simd<f64, N>,
     front_face: Mask<<f64 as SimdElement>::Mask, N>,
     hits:  Mask<<f64 as SimdElement>::Mask, N>,
+    u: Simd<f64, N>,
+    v: Simd<f64, N>,
+    time: Simd<f64, N>,
     materials: PackedOptionalReference<'a, Arc<dyn Material>, N>
     // materials: [Option<&'a Arc<dyn Material>>; N]
 }
 
-impl <const N: usize> Default for PackedHitRecords<'_, N> 
+impl <const N: usize> Default for PackedHitRecords<'_, N>
 where LaneCount<N>: SupportedLaneCount
 {
     fn default() -> Self {
@@ -128,6 +217,9 @@ where LaneCount<N>: SupportedLaneCount
             t: Simd::splat(f64::INFINITY),
             front_face: Mask::splat(false),
             hits: Mask::splat(false),
+            u: Simd::splat(0.0),
+            v: Simd::splat(0.0),
+            time: Simd::splat(0.0),
             materials: PackedOptionalReference::nones(),
         }
     }
@@ -157,8 +249,29 @@ where
     pub fn finalize(&mut self, rays: &PackedRays<N>) {
         self.normals = self.normals.unit_vector();
         self.locations = rays.at_t(self.t);
+        self.time = rays.time();
         self.front_face = rays.directions().dot(&self.normals).simd_lt(Simd::splat(0.0));
         self.normals.assign_masked(&-self.normals, !self.front_face);
+
+        // Standard spherical parameterization of the finalized unit normal; exact
+        // for Sphere, applied uniformly since no other primitive here has its own.
+        self.u = Simd::splat(0.5) + simd_atan2(self.normals.z(), self.normals.x()) / Simd::splat(2.0 * std::f64::consts::PI);
+        self.v = Simd::splat(0.5) - simd_asin(self.normals.y()) / Simd::splat(std::f64::consts::PI);
+    }
+
+    /// Merges in the winning lanes of another packed hit-record buffer (e.g. one
+    /// computed in an `Object::Instance`'s local space and already transformed
+    /// back into world-space normals), keeping whichever hit is closer per lane.
+    pub fn merge(&mut self, other: &PackedHitRecords<'a, N>) {
+        let update_mask = other.hits & other.t.simd_le(self.t);
+
+        self.normals.assign_masked(&other.normals, update_mask);
+        masked_assign(&mut self.t, other.t, update_mask);
+        masked_assign(&mut self.u, other.u, update_mask);
+        masked_assign(&mut self.v, other.v, update_mask);
+        self.hits = self.hits | update_mask;
+
+        self.materials.assign_masked(&other.materials, update_mask.cast())
     }
 
     pub fn at(&self, index: usize) -> Option<HitRecord<'a>> {
@@ -168,6 +281,9 @@ where
                 normal: self.normals.at(index),
                 t: self.t[index],
                 front_face: self.front_face.test(index),
+                u: self.u[index],
+                v: self.v[index],
+                time: self.time[index],
                 material: self.materials.at(index).unwrap(),
             })
         } else {
@@ -270,7 +386,7 @@ impl Sphere {
             let root1 = (neg_half_b - sqrt_discriminant) * inverse_a;
             let root2 = (neg_half_b + sqrt_discriminant) * inverse_a;
             let root1_valid = simd_inside(&root1, t_range);
-            let root2_valid = simd_inside(&root1, t_range);
+            let root2_valid = simd_inside(&root2, t_range);
 
             let root = masked_select(root2, root1, root1_valid);
 
@@ -289,6 +405,11 @@ impl Sphere {
         }
     }
 
+    pub fn aabb(&self) -> Aabb {
+        let r = Vec3::new(self.radius, self.radius, self.radius);
+        Aabb::new(self.center - r, self.center + r)
+    }
+
     pub fn from_table(table: &toml::Table, material_table: &HashMap<String, Arc<dyn Material>>) -> Self where Self: Sized {
         let center = Point3::from_toml(&table["center"]).unwrap();
         let radius = to_float(&table["radius"]).unwrap();
@@ -299,20 +420,454 @@ impl Sphere {
     }
 }
 
+/// A sphere that moves linearly from `center0` at `time0` to `center1` at
+/// `time1`, for motion blur when combined with `Camera`'s shutter interval.
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct MovingSphere {
+    center0: Point3,
+    center1: Point3,
+    time0: f64,
+    time1: f64,
+    radius: f64,
+    material: Arc<dyn Material>,
+}
+
+impl MovingSphere {
+    pub fn new(center0: Point3, center1: Point3, time0: f64, time1: f64, radius: f64, material: &Arc<dyn Material>) -> MovingSphere {
+        MovingSphere { center0, center1, time0, time1, radius, material: material.clone() }
+    }
+
+    fn center_at(&self, time: f64) -> Point3 {
+        let ratio = ((time - self.time0) / (self.time1 - self.time0)).clamp(0.0, 1.0);
+        self.center0 + (self.center1 - self.center0) * ratio
+    }
+
+    /// Lane-wise `center_at`, one interpolated center per ray given its own
+    /// `PackedRays::time()` rather than a single shared time.
+    fn center_at_packed<const N: usize>(&self, time: Simd<f64, N>) -> PackedPoint3<N>
+    where LaneCount<N>: SupportedLaneCount
+    {
+        let ratio = ((time - Simd::splat(self.time0)) / Simd::splat(self.time1 - self.time0))
+            .simd_max(Simd::splat(0.0))
+            .simd_min(Simd::splat(1.0));
+
+        PackedVec3::splat(&self.center0) + PackedVec3::splat(&(self.center1 - self.center0)) * ratio
+    }
+
+    pub fn hit(&self, ray: &Ray, t_range: &std::ops::Range<f64>) -> Option<HitRecord> {
+        let center = self.center_at(ray.time());
+        let center_offset = ray.origin() - center;
+
+        let a = ray.direction().length_squared();
+        let half_b = center_offset.dot(&ray.direction());
+        let c = center_offset.length_squared() - self.radius.powi(2);
+        let discriminant = half_b.powi(2) - a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        let sqrt_discriminant = discriminant.sqrt();
+        let mut root = (-half_b - sqrt_discriminant) / a;
+        if !t_range.contains(&root) {
+            root = (-half_b + sqrt_discriminant) / a;
+            if !t_range.contains(&root) {
+                return None;
+            }
+        }
+
+        let location = ray.at(root);
+
+        Some(HitRecord::new(
+            ray,
+            location,
+            (location - center) / self.radius,
+            root,
+            &self.material,
+        ))
+    }
+
+    pub fn hit_packed<'a, const N: usize>(&'a self, rays: &PackedRays<N>, t_range: &std::ops::Range<f64>, hit_records: &mut PackedHitRecords<'a, N>)
+    where LaneCount<N>: SupportedLaneCount
+    {
+        let center = self.center_at_packed(rays.time());
+
+        let center_offset = rays.origins() - center;
+        let a = rays.directions().length_squared();
+        let inverse_a = Simd::splat(1.0) / a;
+        let half_b = center_offset.dot(&rays.directions());
+        let c = center_offset.length_squared() - Simd::splat(self.radius.powi(2));
+        let discriminant = half_b.mul_add(half_b, -a * c);
+
+        let discriminant_positive = discriminant.simd_ge(Simd::splat(0.0)) & rays.enabled();
+
+        if discriminant_positive.any() {
+            let neg_half_b = negate_simd_float(half_b);
+            let sqrt_discriminant = discriminant.sqrt();
+
+            let root1 = (neg_half_b - sqrt_discriminant) * inverse_a;
+            let root2 = (neg_half_b + sqrt_discriminant) * inverse_a;
+            let root1_valid = simd_inside(&root1, t_range);
+            let root2_valid = simd_inside(&root2, t_range);
+
+            let root = masked_select(root2, root1, root1_valid);
+
+            let valid = (root1_valid | root2_valid) & rays.enabled();
+
+            let locations = rays.at_t(root);
+            let normal = locations - center;
+
+            hit_records.update(
+                rays,
+                &normal,
+                &root,
+                &valid,
+                &self.material
+            )
+        }
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        let r = Vec3::new(self.radius, self.radius, self.radius);
+        let aabb0 = Aabb::new(self.center0 - r, self.center0 + r);
+        let aabb1 = Aabb::new(self.center1 - r, self.center1 + r);
+        aabb0.union(&aabb1)
+    }
+
+    pub fn from_table(table: &toml::Table, material_table: &HashMap<String, Arc<dyn Material>>) -> Self where Self: Sized {
+        let center0 = Point3::from_toml(&table["center0"]).unwrap();
+        let center1 = Point3::from_toml(&table["center1"]).unwrap();
+        let time0 = table.get("time0").and_then(|v| v.as_float()).unwrap_or(0.0);
+        let time1 = table.get("time1").and_then(|v| v.as_float()).unwrap_or(1.0);
+        let radius = to_float(&table["radius"]).unwrap();
+        let material_name = table["material"].as_str().unwrap();
+        let material = material_table.get(material_name).unwrap();
+
+        MovingSphere::new(center0, center1, time0, time1, radius, material)
+    }
+}
+
+/// Wraps a child `Object` with an affine transform, letting any primitive be
+/// rotated, scaled, and translated away from the space it was defined in.
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Instance {
+    child: Object,
+    transform: Mat4,
+    inverse_transform: Mat4,
+    inverse_transpose: Mat4,
+    /// -1.0 when `transform` mirrors handedness (e.g. a negative scale
+    /// factor), otherwise 1.0. Only applied to children whose outward normal
+    /// is a pseudovector (see `Object::normal_is_pseudovector`) -- those
+    /// normals encode a handedness convention that `inverse_transpose` alone
+    /// doesn't correct for under a mirrored transform, while a true-covector
+    /// normal (e.g. `Sphere`'s) already comes out right without this sign.
+    normal_sign: f64,
+}
+
+impl Instance {
+    pub fn new(child: Object, transform: Mat4) -> Instance {
+        let inverse_transform = transform.inverse().expect("Instance transform must be invertible");
+        let inverse_transpose = inverse_transform.transpose();
+        let normal_sign = if transform.linear_determinant() < 0.0 { -1.0 } else { 1.0 };
+
+        Instance {
+            child,
+            transform,
+            inverse_transform,
+            inverse_transpose,
+            normal_sign,
+        }
+    }
+
+    pub fn hit(&self, ray: &Ray, t_range: &std::ops::Range<f64>) -> Option<HitRecord> {
+        let local_origin = self.inverse_transform.transform_point(ray.origin());
+        let local_direction = self.inverse_transform.transform_vector(ray.direction());
+        let local_ray = Ray::new_with_time(local_origin, local_direction, ray.time());
+
+        let local_hit = self.child.hit(&local_ray, t_range)?;
+
+        // `local_hit.normal()` is the *shading* normal -- already flipped to
+        // oppose `local_direction` -- not the ray-independent outward normal.
+        // Since the outer `ray` and `local_direction` are related by the same
+        // map that built `inverse_transpose`, transforming the shading normal
+        // directly would make the outer front-facing test collapse to a
+        // constant rather than actually depend on which side `ray` hit; undo
+        // the local flip first so `HitRecord::new` re-derives front-facing
+        // against the outer ray correctly.
+        let location = ray.at(local_hit.t());
+        let local_outward = if local_hit.front_face() { local_hit.normal() } else { -local_hit.normal() };
+        let mut normal = self.inverse_transpose.transform_vector(local_outward);
+        if self.child.normal_is_pseudovector() {
+            normal = normal * self.normal_sign;
+        }
+
+        Some(HitRecord::new(ray, location, normal.unit(), local_hit.t(), local_hit.material()))
+    }
+
+    pub fn hit_packed<'a, const N: usize>(&'a self, rays: &PackedRays<N>, t_range: &std::ops::Range<f64>, hit_records: &mut PackedHitRecords<'a, N>)
+    where LaneCount<N>: SupportedLaneCount
+    {
+        let local_origins = self.inverse_transform.transform_packed_point(&rays.origins());
+        let local_directions = self.inverse_transform.transform_packed_vector(&rays.directions());
+        let local_rays = PackedRays::new_with_enable_and_time(local_origins, local_directions, rays.enabled(), rays.time());
+
+        let mut local_hit_records = PackedHitRecords::<N>::default();
+        self.child.hit_packed(&local_rays, t_range, &mut local_hit_records);
+
+        if !local_hit_records.hits().any() {
+            return;
+        }
+
+        // Unlike the scalar path, `local_hit_records` is never finalized
+        // against `local_rays` here -- that would commit its normals to
+        // local-ray-relative shading normals (and a local front_face that
+        // `merge` doesn't even carry over), the same bug as the scalar
+        // `hit` had. Instead we normalize the raw outward normal the child
+        // wrote via `update`, transform it, and let the top-level
+        // `PackedHitRecords::finalize` (called once, after merging every
+        // object, against the real world-space `rays`) derive front-facing
+        // correctly from it.
+        let mut normals = self.inverse_transpose.transform_packed_vector(&local_hit_records.normals.unit_vector());
+        if self.child.normal_is_pseudovector() {
+            normals = normals * self.normal_sign;
+        }
+        local_hit_records.normals = normals.unit_vector();
+
+        hit_records.merge(&local_hit_records);
+    }
+
+    /// Bounds the child's box by transforming its 8 corners and taking their union,
+    /// since an arbitrary affine transform doesn't map an AABB to another AABB directly.
+    pub fn aabb(&self) -> Aabb {
+        let child_aabb = self.child.aabb();
+        let min = child_aabb.min();
+        let max = child_aabb.max();
+
+        let corners = [
+            Point3::new(min.x(), min.y(), min.z()),
+            Point3::new(min.x(), min.y(), max.z()),
+            Point3::new(min.x(), max.y(), min.z()),
+            Point3::new(min.x(), max.y(), max.z()),
+            Point3::new(max.x(), min.y(), min.z()),
+            Point3::new(max.x(), min.y(), max.z()),
+            Point3::new(max.x(), max.y(), min.z()),
+            Point3::new(max.x(), max.y(), max.z()),
+        ];
+
+        Aabb::from_points(&corners.map(|corner| self.transform.transform_point(corner)))
+    }
+
+    pub fn from_table(table: &toml::Table, material_table: &HashMap<String, Arc<dyn Material>>) -> Instance {
+        let child_table = table["child"].as_table().unwrap();
+        let child = load_object_from_toml(child_table, material_table);
+
+        let mut transform = Mat4::identity();
+
+        if let Some(v) = table.get("scale") {
+            transform = Mat4::scaling(Vec3::from_toml(v).unwrap()).mul(&transform);
+        }
+
+        if let Some(v) = table.get("rotate_x") {
+            transform = Mat4::rotation_x(to_float(v).unwrap().to_radians()).mul(&transform);
+        }
+
+        if let Some(v) = table.get("rotate_y") {
+            transform = Mat4::rotation_y(to_float(v).unwrap().to_radians()).mul(&transform);
+        }
+
+        if let Some(v) = table.get("rotate_z") {
+            transform = Mat4::rotation_z(to_float(v).unwrap().to_radians()).mul(&transform);
+        }
+
+        if let Some(v) = table.get("translate") {
+            transform = Mat4::translation(Vec3::from_toml(v).unwrap()).mul(&transform);
+        }
+
+        Instance::new(child, transform)
+    }
+}
+
+const TRIANGLE_EPSILON: f64 = 1E-8;
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Triangle {
+    v0: Point3,
+    v1: Point3,
+    v2: Point3,
+    material: Arc<dyn Material>,
+}
+
+impl Triangle {
+    pub fn new(v0: Point3, v1: Point3, v2: Point3, material: &Arc<dyn Material>) -> Triangle {
+        Triangle { v0, v1, v2, material: material.clone() }
+    }
+
+    pub fn hit(&self, ray: &Ray, t_range: &std::ops::Range<f64>) -> Option<HitRecord> {
+        let e1 = self.v1 - self.v0;
+        let e2 = self.v2 - self.v0;
+
+        let h = ray.direction().cross(&e2);
+        let a = e1.dot(&h);
+        if a.abs() < TRIANGLE_EPSILON {
+            return None;
+        }
+
+        let f = 1.0 / a;
+        let s = ray.origin() - self.v0;
+        let u = f * s.dot(&h);
+        if u < 0.0 || u > 1.0 {
+            return None;
+        }
+
+        let q = s.cross(&e1);
+        let v = f * ray.direction().dot(&q);
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let t = f * e2.dot(&q);
+        if !t_range.contains(&t) {
+            return None;
+        }
+
+        let location = ray.at(t);
+        let outward_normal = e1.cross(&e2).unit();
+
+        Some(HitRecord::new(ray, location, outward_normal, t, &self.material))
+    }
+
+    pub fn hit_packed<'a, const N: usize>(&'a self, rays: &PackedRays<N>, t_range: &std::ops::Range<f64>, hit_records: &mut PackedHitRecords<'a, N>)
+    where LaneCount<N>: SupportedLaneCount
+    {
+        let e1 = self.v1 - self.v0;
+        let e2 = self.v2 - self.v0;
+        let packed_e1 = PackedVec3::<N>::splat(&e1);
+        let packed_e2 = PackedVec3::<N>::splat(&e2);
+        let packed_v0 = PackedPoint3::<N>::splat(&self.v0);
+
+        let h = rays.directions().cross(&packed_e2);
+        let a = packed_e1.dot(&h);
+
+        let valid_a = a.abs().simd_gt(Simd::splat(TRIANGLE_EPSILON)) & rays.enabled();
+
+        if !valid_a.any() {
+            return;
+        }
+
+        let inv_a = Simd::splat(1.0) / a;
+        let s = rays.origins() - packed_v0;
+        let u = inv_a * s.dot(&h);
+
+        let valid_u = valid_a & u.simd_ge(Simd::splat(0.0)) & u.simd_le(Simd::splat(1.0));
+
+        let q = s.cross(&packed_e1);
+        let v = inv_a * rays.directions().dot(&q);
+
+        let valid_v = valid_u & v.simd_ge(Simd::splat(0.0)) & (u + v).simd_le(Simd::splat(1.0));
+
+        let t = inv_a * packed_e2.dot(&q);
+        let valid = valid_v & simd_inside(&t, t_range);
+
+        if valid.any() {
+            let normal = PackedVec3::<N>::splat(&e1.cross(&e2).unit());
+            hit_records.update(rays, &normal, &t, &valid, &self.material);
+        }
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        Aabb::from_points(&[self.v0, self.v1, self.v2])
+    }
+
+    pub fn from_table(table: &toml::Table, material_table: &HashMap<String, Arc<dyn Material>>) -> Triangle {
+        let v0 = Point3::from_toml(&table["v0"]).unwrap();
+        let v1 = Point3::from_toml(&table["v1"]).unwrap();
+        let v2 = Point3::from_toml(&table["v2"]).unwrap();
+        let material_name = table["material"].as_str().unwrap();
+        let material = material_table.get(material_name).unwrap();
+
+        Triangle::new(v0, v1, v2, material)
+    }
+}
+
+/// A collection of triangles, each with its own material, tested as one `Object`.
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Mesh {
+    triangles: Vec<Triangle>,
+}
+
+impl Mesh {
+    pub fn new(triangles: Vec<Triangle>) -> Mesh {
+        Mesh { triangles }
+    }
+
+    pub fn hit(&self, ray: &Ray, t_range: &std::ops::Range<f64>) -> Option<HitRecord> {
+        self.triangles.iter()
+            .filter_map(|triangle| triangle.hit(ray, t_range))
+            .min_by_key(|h| ordered_float::OrderedFloat::from(h.t()))
+    }
+
+    pub fn hit_packed<'a, const N: usize>(&'a self, rays: &PackedRays<N>, t_range: &std::ops::Range<f64>, hit_records: &mut PackedHitRecords<'a, N>)
+    where LaneCount<N>: SupportedLaneCount
+    {
+        for triangle in &self.triangles {
+            triangle.hit_packed(rays, t_range, hit_records);
+        }
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        self.triangles.iter()
+            .map(|triangle| triangle.aabb())
+            .fold(Aabb::empty(), |acc, b| acc.union(&b))
+    }
+
+    pub fn from_table(table: &toml::Table, material_table: &HashMap<String, Arc<dyn Material>>) -> Mesh {
+        if let Some(obj_path) = table.get("obj").and_then(|v| v.as_str()) {
+            return crate::obj_loader::load_obj_mesh(std::path::Path::new(obj_path));
+        }
+
+        let material_name = table["material"].as_str().unwrap();
+        let material = material_table.get(material_name).unwrap();
+
+        let triangles = table["triangles"].as_array().unwrap().iter().map(|value| {
+            let vertices = value.as_array().unwrap();
+            assert!(vertices.len() == 3, "Each mesh triangle needs exactly 3 vertices");
+
+            Triangle::new(
+                Point3::from_toml(&vertices[0]).unwrap(),
+                Point3::from_toml(&vertices[1]).unwrap(),
+                Point3::from_toml(&vertices[2]).unwrap(),
+                material,
+            )
+        }).collect();
+
+        Mesh::new(triangles)
+    }
+}
+
 #[derive(Debug)]
 #[derive(Clone)]
 pub struct HitResult {
     attenuation: Color,
-    scattered_ray: Option<Ray>
+    scattered_ray: Option<Ray>,
+    emitted: Color,
 }
 
 impl HitResult {
     pub fn new_absorbed(attenuation: Color) -> HitResult {
-        HitResult { attenuation: attenuation, scattered_ray: None }
+        HitResult { attenuation: attenuation, scattered_ray: None, emitted: Color::black() }
     }
 
     pub fn new_scattered(attenuation: Color, scattered_ray: Ray) -> HitResult {
-        HitResult { attenuation: attenuation, scattered_ray: Some(scattered_ray) }
+        HitResult { attenuation: attenuation, scattered_ray: Some(scattered_ray), emitted: Color::black() }
+    }
+
+    /// Non-scattering result for an emissive material: no bounce ray, the hit
+    /// just contributes its own emitted color.
+    pub fn new_emitted(emitted: Color) -> HitResult {
+        HitResult { attenuation: Color::black(), scattered_ray: None, emitted }
     }
 
     pub fn attenuation(&self) -> Color {
@@ -322,4 +877,8 @@ impl HitResult {
     pub fn scattered_ray(&self) -> Option<&Ray> {
         self.scattered_ray.as_ref()
     }
+
+    pub fn emitted(&self) -> Color {
+        self.emitted
+    }
 }
\ No newline at end of file
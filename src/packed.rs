@@ -1,6 +1,6 @@
 use std::{fmt::Debug, cmp::Ordering, ops::RangeBounds};
 use array_macro::array;
-use num::{Float, Unsigned, Integer};
+use num::{Float, Unsigned, Integer, ToPrimitive, Num};
 
 // marker trait for scaler objects
 pub trait Scaler : Copy + Clone + Debug{
@@ -20,7 +20,186 @@ impl Scaler for u8    { type MaskType = u8;}
 impl Scaler for usize { type MaskType = usize;}
 impl Scaler for bool  { type MaskType = u8;}
 
-pub trait Mask: 
+/// IEEE-754 binary16 value, stored as raw bits. Rust has no native arithmetic
+/// `f16`, so every operation on it widens to `f32`, computes, and narrows the
+/// result back down with round-to-nearest-even.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug)]
+pub struct F16(pub u16);
+
+impl F16 {
+    pub const ZERO: F16 = F16(0);
+
+    #[inline]
+    pub fn from_f32(value: f32) -> F16 {
+        F16(f32_bits_to_f16_bits(value.to_bits()))
+    }
+
+    #[inline]
+    pub fn to_f32(self) -> f32 {
+        f32::from_bits(f16_bits_to_f32_bits(self.0))
+    }
+}
+
+impl Default for F16 {
+    #[inline]
+    fn default() -> Self {
+        F16::ZERO
+    }
+}
+
+impl PartialEq for F16 {
+    #[inline]
+    fn eq(&self, other: &F16) -> bool {
+        self.to_f32() == other.to_f32()
+    }
+}
+
+impl PartialOrd for F16 {
+    #[inline]
+    fn partial_cmp(&self, other: &F16) -> Option<Ordering> {
+        self.to_f32().partial_cmp(&other.to_f32())
+    }
+}
+
+impl std::ops::Add for F16 {
+    type Output = F16;
+
+    #[inline]
+    fn add(self, rhs: F16) -> F16 {
+        F16::from_f32(self.to_f32() + rhs.to_f32())
+    }
+}
+
+impl std::ops::Sub for F16 {
+    type Output = F16;
+
+    #[inline]
+    fn sub(self, rhs: F16) -> F16 {
+        F16::from_f32(self.to_f32() - rhs.to_f32())
+    }
+}
+
+impl std::ops::Mul for F16 {
+    type Output = F16;
+
+    #[inline]
+    fn mul(self, rhs: F16) -> F16 {
+        F16::from_f32(self.to_f32() * rhs.to_f32())
+    }
+}
+
+impl std::ops::Div for F16 {
+    type Output = F16;
+
+    #[inline]
+    fn div(self, rhs: F16) -> F16 {
+        F16::from_f32(self.to_f32() / rhs.to_f32())
+    }
+}
+
+impl std::ops::Neg for F16 {
+    type Output = F16;
+
+    #[inline]
+    fn neg(self) -> F16 {
+        F16(self.0 ^ 0x8000)
+    }
+}
+
+impl Scaler for F16 { type MaskType = u16;}
+
+/// Rounds `value >> shift` to the nearest integer, ties to even, matching the
+/// rounding IEEE-754 conversions require.
+#[inline]
+fn round_shift(value: u32, shift: u32) -> u32 {
+    if shift == 0 {
+        return value;
+    }
+    let half = 1u32 << (shift - 1);
+    let mask = (1u32 << shift) - 1;
+    let remainder = value & mask;
+    let mut shifted = value >> shift;
+    if remainder > half || (remainder == half && (shifted & 1) != 0) {
+        shifted += 1;
+    }
+    shifted
+}
+
+/// Widens an IEEE-754 binary16 bit pattern to a binary32 bit pattern.
+fn f16_bits_to_f32_bits(half: u16) -> u32 {
+    let sign = (half as u32 & 0x8000) << 16;
+    let exponent = (half as u32 >> 10) & 0x1f;
+    let mantissa = half as u32 & 0x3ff;
+
+    if exponent == 0 {
+        if mantissa == 0 {
+            // zero
+            sign
+        } else {
+            // subnormal half -> normalized single
+            let mut e: i32 = -1;
+            let mut m = mantissa;
+            while m & 0x400 == 0 {
+                m <<= 1;
+                e += 1;
+            }
+            m &= 0x3ff;
+            let biased_exponent = (127 - 15 - e) as u32;
+            sign | (biased_exponent << 23) | (m << 13)
+        }
+    } else if exponent == 0x1f {
+        // infinity or NaN
+        sign | (0xff << 23) | (mantissa << 13)
+    } else {
+        let biased_exponent = exponent + (127 - 15);
+        sign | (biased_exponent << 23) | (mantissa << 13)
+    }
+}
+
+/// Narrows an IEEE-754 binary32 bit pattern to a binary16 bit pattern,
+/// rounding to nearest even.
+fn f32_bits_to_f16_bits(bits: u32) -> u16 {
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+
+    if exponent == 0xff {
+        // infinity or NaN; keep a set mantissa bit so NaNs stay NaN
+        let half_mantissa: u16 = if mantissa != 0 { 0x200 } else { 0 };
+        return sign | 0x7c00 | half_mantissa;
+    }
+
+    let half_exponent = exponent - 127 + 15;
+
+    if half_exponent >= 0x1f {
+        // overflow -> infinity
+        return sign | 0x7c00;
+    }
+
+    if half_exponent <= 0 {
+        if half_exponent < -10 {
+            // magnitude too small to represent -> zero
+            return sign;
+        }
+        // subnormal half: shift the implicit-one mantissa down by the
+        // additional exponent deficit
+        let full_mantissa = mantissa | 0x80_0000;
+        let shift = (14 - half_exponent) as u32;
+        let rounded = round_shift(full_mantissa, shift);
+        return sign | (rounded as u16);
+    }
+
+    let rounded_mantissa = round_shift(mantissa, 13);
+    if rounded_mantissa & 0x400 != 0 {
+        // rounding carried into the exponent
+        return sign | (((half_exponent + 1) as u16) << 10);
+    }
+
+    sign | ((half_exponent as u16) << 10) | (rounded_mantissa as u16 & 0x3ff)
+}
+
+pub trait Mask:
     Integer + Unsigned + Scaler + Ord + std::ops::Not<Output = Self> + 
     std::ops::BitAnd<Self, Output = Self> + std::ops::BitOr<Self, Output = Self> + 
     std::ops::BitXor<Self, Output = Self> 
@@ -84,8 +263,10 @@ pub struct Packed<T: Scaler, const N: usize>
 
 pub type PackedF64<const N: usize> = Packed<f64, N>;
 pub type PackedF32<const N: usize> = Packed<f32, N>;
+pub type PackedF16<const N: usize> = Packed<F16, N>;
 pub type PackedF64Mask<const N: usize> = Packed<<f64 as Scaler>::MaskType, N>;
 pub type PackedF32Mask<const N: usize> = Packed<<f32 as Scaler>::MaskType, N>;
+pub type PackedF16Mask<const N: usize> = Packed<<F16 as Scaler>::MaskType, N>;
 // pub type PackedBool<const N: usize> = Packed<bool, N>;
 
 impl <T, const N: usize> Packed<T, N>
@@ -123,8 +304,8 @@ where T: Scaler
     }
 
     #[inline]
-    pub fn select_masked<M>(&self, values: Packed<T, N>, mask: Packed<M, N>) -> Packed<T, N> 
-    where 
+    pub fn select_masked<M>(&self, values: Packed<T, N>, mask: Packed<M, N>) -> Packed<T, N>
+    where
         T: Scaler<MaskType = M>,
         M: Mask
     {
@@ -136,6 +317,65 @@ where T: Scaler
             }; N]
         )
     }
+
+    /// Reads only the lanes selected by `mask`, leaving the rest at
+    /// `T::default()`. Scalar fallback for the type-specific AVX2 fast
+    /// paths (e.g. `Packed::<f64,N>::load_masked_f64`).
+    #[inline]
+    pub fn load_masked<M>(base: &[T], mask: Packed<M, N>) -> Packed<T, N>
+    where
+        T: Scaler<MaskType = M> + Default,
+        M: Mask
+    {
+        Packed::from(
+            array![i => if mask[i].to_bool() { base[i] } else { T::default() }; N]
+        )
+    }
+
+    /// Writes only the lanes selected by `mask` into `base`.
+    #[inline]
+    pub fn store_masked<M>(&self, base: &mut [T], mask: Packed<M, N>)
+    where
+        T: Scaler<MaskType = M>,
+        M: Mask
+    {
+        for i in 0..N {
+            if mask[i].to_bool() {
+                base[i] = self[i];
+            }
+        }
+    }
+
+    /// Reads `base[indices[i]]` for every lane selected by `mask`, leaving
+    /// the rest at `T::default()` — the SoA equivalent of `load_masked`
+    /// when each lane needs a different index (e.g. per-ray BVH node or
+    /// triangle-vertex lookups).
+    #[inline]
+    pub fn gather<M>(base: &[T], indices: Packed<usize, N>, mask: Packed<M, N>) -> Packed<T, N>
+    where
+        T: Scaler<MaskType = M> + Default,
+        M: Mask
+    {
+        Packed::from(
+            array![i => if mask[i].to_bool() { base[indices[i]] } else { T::default() }; N]
+        )
+    }
+
+    /// Writes lane `i` to `base[indices[i]]` for every lane selected by
+    /// `mask`. AVX2/AVX-512 have no true scatter instruction usable here,
+    /// so this is always the scalar loop.
+    #[inline]
+    pub fn scatter<M>(&self, base: &mut [T], indices: Packed<usize, N>, mask: Packed<M, N>)
+    where
+        T: Scaler<MaskType = M>,
+        M: Mask
+    {
+        for i in 0..N {
+            if mask[i].to_bool() {
+                base[indices[i]] = self[i];
+            }
+        }
+    }
 }
 
 impl <T, const N: usize> Default for Packed<T,N>
@@ -680,9 +920,164 @@ where
     }
 }
 
-impl <T, const N: usize> Packed<T, N> 
+/// Evaluates `sin(pi * r)`/`cos(pi * r)` for `r` already reduced to
+/// `[-0.25, 0.25]`, via the Taylor polynomials of `sin`/`cos` about zero
+/// (accurate to a few ULPs over that narrow range, since `|pi * r| <= pi/4`).
+#[inline]
+fn sin_cos_pi_reduced<T: Float>(r: T) -> (T, T) {
+    let pi = T::from(std::f64::consts::PI).unwrap();
+    let u = r * pi;
+    let u2 = u * u;
+
+    let sin_poly = T::one()
+        - u2 / T::from(6.0).unwrap()
+        + u2 * u2 / T::from(120.0).unwrap()
+        - u2 * u2 * u2 / T::from(5040.0).unwrap()
+        + u2 * u2 * u2 * u2 / T::from(362880.0).unwrap();
+
+    let cos_poly = T::one()
+        - u2 / T::from(2.0).unwrap()
+        + u2 * u2 / T::from(24.0).unwrap()
+        - u2 * u2 * u2 / T::from(720.0).unwrap()
+        + u2 * u2 * u2 * u2 / T::from(40320.0).unwrap();
+
+    (u * sin_poly, cos_poly)
+}
+
+/// Computes `(sin(pi * x), cos(pi * x))` by range-reducing `x` to the
+/// nearest half-integer `k / 2` and a remainder `r` in `[-0.25, 0.25]`,
+/// evaluating the Taylor polynomials on `r`, then selecting/negating
+/// with the low two bits of `k` for the quadrant — the same range-reduce
+/// structure real SIMD libm implementations use, kept lane-uniform so every
+/// element in a `Packed<T, N>` runs the identical sequence of operations.
+#[inline]
+fn sin_cos_pi_scalar<T: Float>(x: T) -> (T, T) {
+    if !x.is_finite() {
+        return (T::nan(), T::nan());
+    }
+
+    let k = (x * T::from(2.0).unwrap()).round();
+    let r = x - k * T::from(0.5).unwrap();
+
+    let (s, c) = sin_cos_pi_reduced(r);
+
+    let quadrant = k.to_i64().unwrap_or(0).rem_euclid(4);
+
+    match quadrant {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    }
+}
+
+/// Rounds to the nearest integer, ties to even. `num::Float` has no native
+/// `round_ties_even`, so this is built from `floor` plus a parity check.
+#[inline]
+fn round_ties_even_scalar<T: Float>(x: T) -> T {
+    let floor = x.floor();
+    let diff = x - floor;
+    let half = T::from(0.5).unwrap();
+
+    if diff < half {
+        floor
+    } else if diff > half {
+        floor + T::one()
+    } else {
+        let floor_is_even = (floor / T::from(2.0).unwrap()).fract() == T::zero();
+        if floor_is_even {
+            floor
+        } else {
+            floor + T::one()
+        }
+    }
+}
+
+/// Rounding mode used by `ConvertTo`/`ConvertFrom` when narrowing a float
+/// lane to an integer one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    Truncate,
+    NearestEven,
+    Floor,
+    Ceil,
+}
+
+/// Lane-wise conversion into `Packed<U, N>`, mirroring the `ConvertFrom`/
+/// `ConvertTo` pair from the vector-math scalar-type traits. Implemented via
+/// the blanket impl below plus one `ConvertFrom` impl per concrete pair, the
+/// same split `PackedPartialEq`/`PackedPartialOrd` already use.
+pub trait ConvertTo<U: Scaler, const N: usize> {
+    fn convert_rounding(&self, rounding: RoundingMode) -> Packed<U, N>;
+
+    /// Convenience for the common case; uses round-to-nearest-even.
+    #[inline]
+    fn convert(&self) -> Packed<U, N> {
+        self.convert_rounding(RoundingMode::NearestEven)
+    }
+}
+
+pub trait ConvertFrom<T: Scaler, const N: usize>: Scaler + Sized {
+    fn convert_from(value: &Packed<T, N>, rounding: RoundingMode) -> Packed<Self, N>;
+}
+
+impl <T, U, const N: usize> ConvertTo<U, N> for Packed<T, N>
+where
+    T: Scaler,
+    U: Scaler + ConvertFrom<T, N>,
+{
+    #[inline]
+    fn convert_rounding(&self, rounding: RoundingMode) -> Packed<U, N> {
+        U::convert_from(self, rounding)
+    }
+}
+
+impl <T, const N: usize> Packed<T, N>
 where T: Scaler + Float
 {
+    #[inline]
+    pub fn sin(&self) -> Packed<T, N> {
+        let inv_pi = T::one() / T::from(std::f64::consts::PI).unwrap();
+        Packed::from(
+            array![i => sin_cos_pi_scalar(self.0[i] * inv_pi).0; N]
+        )
+    }
+
+    #[inline]
+    pub fn cos(&self) -> Packed<T, N> {
+        let inv_pi = T::one() / T::from(std::f64::consts::PI).unwrap();
+        Packed::from(
+            array![i => sin_cos_pi_scalar(self.0[i] * inv_pi).1; N]
+        )
+    }
+
+    /// Fused `(sin(pi * x), cos(pi * x))`, avoiding the duplicated range
+    /// reduction that calling `sin`/`cos` separately (after dividing by
+    /// `pi`) would incur.
+    #[inline]
+    pub fn sin_cos_pi(&self) -> (Packed<T, N>, Packed<T, N>) {
+        let pairs: [(T, T); N] = array![i => sin_cos_pi_scalar(self.0[i]); N];
+        (
+            Packed::from(array![i => pairs[i].0; N]),
+            Packed::from(array![i => pairs[i].1; N]),
+        )
+    }
+
+    /// Rounds every lane to an integer-valued `T` using the given
+    /// `RoundingMode`, the shared first step behind every float-to-int
+    /// `ConvertFrom` impl below.
+    #[inline]
+    pub fn round_with_mode(&self, mode: RoundingMode) -> Packed<T, N> {
+        match mode {
+            RoundingMode::Truncate => self.trunc(),
+            RoundingMode::Floor => self.floor(),
+            RoundingMode::Ceil => self.ceil(),
+            RoundingMode::NearestEven => Packed::from(
+                array![i => round_ties_even_scalar(self.0[i]); N]
+            ),
+        }
+    }
+
     #[inline]
     pub fn floor(&self) -> Packed<T, N> {
         Packed::from(
@@ -877,4 +1272,939 @@ impl <const N: usize> Packed<f64, N> {
             _mm256_maskstore_pd(dest_ptr, mask_reg, value_reg);
         }
     }
-}
\ No newline at end of file
+
+    /// Reads only the lanes selected by `mask` from `base`, using
+    /// `_mm256_maskload_pd` when AVX2 is available.
+    #[inline]
+    pub fn load_masked_f64(base: &[f64], mask: Packed<u64, N>) -> Packed<f64, N> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                unsafe {
+                    return load_masked_f64_avx2(base, mask);
+                }
+            }
+        }
+
+        Packed::<f64, N>::load_masked(base, mask)
+    }
+
+    /// Writes only the lanes selected by `mask` into `base`, completing the
+    /// `_mm256_maskstore_pd` family alongside `assign_masked_f64`.
+    #[inline]
+    pub fn store_masked_f64(&self, base: &mut [f64], mask: Packed<u64, N>) {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                unsafe {
+                    self.store_masked_f64_avx2(base, mask);
+                    return;
+                }
+            }
+        }
+
+        self.store_masked(base, mask);
+    }
+
+    #[inline]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[target_feature(enable = "avx2")]
+    unsafe fn store_masked_f64_avx2(&self, base: &mut [f64], mask: Packed<u64, N>) {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::*;
+
+        for i in 0..(N/4) {
+            let mask_ptr: *const u64 = mask.0.as_ptr().wrapping_add(i * 4);
+            let mask_reg = _mm256_loadu_si256(std::mem::transmute(mask_ptr));
+
+            let value_ptr: *const f64 = self.0.as_ptr().wrapping_add(i * 4);
+            let value_reg = _mm256_loadu_pd(value_ptr);
+
+            let dest_ptr: *mut f64 = base.as_mut_ptr().wrapping_add(i * 4);
+            _mm256_maskstore_pd(dest_ptr, mask_reg, value_reg);
+        }
+
+        for i in (N/4*4)..N {
+            if mask.0[i].to_bool() {
+                base[i] = self.0[i];
+            }
+        }
+    }
+
+    /// Gathers `base[indices[i]]` for every lane selected by `mask`, using
+    /// `_mm256_mask_i64gather_pd` when AVX2 is available — lets ray-packet
+    /// traversal fetch per-ray BVH node/triangle data in one vectorized op
+    /// instead of a per-element loop.
+    #[inline]
+    pub fn gather_f64(base: &[f64], indices: Packed<usize, N>, mask: Packed<u64, N>) -> Packed<f64, N> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                unsafe {
+                    return gather_f64_avx2(base, indices, mask);
+                }
+            }
+        }
+
+        Packed::<f64, N>::gather(base, indices, mask)
+    }
+}
+
+#[inline]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn load_masked_f64_avx2<const N: usize>(base: &[f64], mask: Packed<u64, N>) -> Packed<f64, N> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    let mut result: std::mem::MaybeUninit<Packed<f64, N>> = std::mem::MaybeUninit::uninit();
+    let result_ptr: *mut f64 = std::mem::transmute(result.as_mut_ptr());
+
+    for i in 0..(N/4) {
+        let mask_ptr: *const u64 = mask.0.as_ptr().wrapping_add(i * 4);
+        let mask_reg = _mm256_loadu_si256(std::mem::transmute(mask_ptr));
+
+        let base_ptr = base.as_ptr().wrapping_add(i * 4);
+        let loaded = _mm256_maskload_pd(base_ptr, mask_reg);
+
+        _mm256_storeu_pd(result_ptr.wrapping_add(i * 4), loaded);
+    }
+
+    let mut result = result.assume_init();
+    for i in (N/4*4)..N {
+        result.0[i] = if mask.0[i].to_bool() { base[i] } else { 0.0 };
+    }
+    result
+}
+
+#[inline]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn gather_f64_avx2<const N: usize>(base: &[f64], indices: Packed<usize, N>, mask: Packed<u64, N>) -> Packed<f64, N> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    let mut result: std::mem::MaybeUninit<Packed<f64, N>> = std::mem::MaybeUninit::uninit();
+    let result_ptr: *mut f64 = std::mem::transmute(result.as_mut_ptr());
+
+    for i in 0..(N/4) {
+        let index_array: [i64; 4] = std::array::from_fn(|j| indices.0[i * 4 + j] as i64);
+        let index_reg = _mm256_loadu_si256(std::mem::transmute(index_array.as_ptr()));
+
+        let mask_ptr: *const f64 = std::mem::transmute(mask.0.as_ptr().wrapping_add(i * 4));
+        let mask_reg = _mm256_loadu_pd(mask_ptr);
+
+        let gathered = _mm256_mask_i64gather_pd(_mm256_setzero_pd(), base.as_ptr(), index_reg, mask_reg, 8);
+        _mm256_storeu_pd(result_ptr.wrapping_add(i * 4), gathered);
+    }
+
+    let mut result = result.assume_init();
+    for i in (N/4*4)..N {
+        result.0[i] = if mask.0[i].to_bool() { base[indices.0[i]] } else { 0.0 };
+    }
+    result
+}
+
+impl <const N: usize> Packed<f32, N> {
+
+    /// Reads only the lanes selected by `mask` from `base`, using
+    /// `_mm256_maskload_ps` when AVX2 is available.
+    #[inline]
+    pub fn load_masked_f32(base: &[f32], mask: Packed<u32, N>) -> Packed<f32, N> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                unsafe {
+                    return load_masked_f32_avx2(base, mask);
+                }
+            }
+        }
+
+        Packed::<f32, N>::load_masked(base, mask)
+    }
+
+    /// Writes only the lanes selected by `mask` into `base`, using
+    /// `_mm256_maskstore_ps` when AVX2 is available.
+    #[inline]
+    pub fn store_masked_f32(&self, base: &mut [f32], mask: Packed<u32, N>) {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                unsafe {
+                    self.store_masked_f32_avx2(base, mask);
+                    return;
+                }
+            }
+        }
+
+        self.store_masked(base, mask);
+    }
+
+    #[inline]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[target_feature(enable = "avx2")]
+    unsafe fn store_masked_f32_avx2(&self, base: &mut [f32], mask: Packed<u32, N>) {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::*;
+
+        for i in 0..(N/8) {
+            let mask_ptr: *const u32 = mask.0.as_ptr().wrapping_add(i * 8);
+            let mask_reg = _mm256_loadu_si256(std::mem::transmute(mask_ptr));
+
+            let value_ptr: *const f32 = self.0.as_ptr().wrapping_add(i * 8);
+            let value_reg = _mm256_loadu_ps(value_ptr);
+
+            let dest_ptr: *mut f32 = base.as_mut_ptr().wrapping_add(i * 8);
+            _mm256_maskstore_ps(dest_ptr, mask_reg, value_reg);
+        }
+
+        for i in (N/8*8)..N {
+            if mask.0[i].to_bool() {
+                base[i] = self.0[i];
+            }
+        }
+    }
+
+    /// Gathers `base[indices[i]]` for every lane selected by `mask`, using
+    /// `_mm256_mask_i32gather_ps` when AVX2 is available. Indices are
+    /// truncated to `i32`, matching the index width the intrinsic takes.
+    #[inline]
+    pub fn gather_f32(base: &[f32], indices: Packed<usize, N>, mask: Packed<u32, N>) -> Packed<f32, N> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                unsafe {
+                    return gather_f32_avx2(base, indices, mask);
+                }
+            }
+        }
+
+        Packed::<f32, N>::gather(base, indices, mask)
+    }
+}
+
+#[inline]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn load_masked_f32_avx2<const N: usize>(base: &[f32], mask: Packed<u32, N>) -> Packed<f32, N> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    let mut result: std::mem::MaybeUninit<Packed<f32, N>> = std::mem::MaybeUninit::uninit();
+    let result_ptr: *mut f32 = std::mem::transmute(result.as_mut_ptr());
+
+    for i in 0..(N/8) {
+        let mask_ptr: *const u32 = mask.0.as_ptr().wrapping_add(i * 8);
+        let mask_reg = _mm256_loadu_si256(std::mem::transmute(mask_ptr));
+
+        let base_ptr = base.as_ptr().wrapping_add(i * 8);
+        let loaded = _mm256_maskload_ps(base_ptr, mask_reg);
+
+        _mm256_storeu_ps(result_ptr.wrapping_add(i * 8), loaded);
+    }
+
+    let mut result = result.assume_init();
+    for i in (N/8*8)..N {
+        result.0[i] = if mask.0[i].to_bool() { base[i] } else { 0.0 };
+    }
+    result
+}
+
+#[inline]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn gather_f32_avx2<const N: usize>(base: &[f32], indices: Packed<usize, N>, mask: Packed<u32, N>) -> Packed<f32, N> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    let mut result: std::mem::MaybeUninit<Packed<f32, N>> = std::mem::MaybeUninit::uninit();
+    let result_ptr: *mut f32 = std::mem::transmute(result.as_mut_ptr());
+
+    for i in 0..(N/8) {
+        let index_array: [i32; 8] = std::array::from_fn(|j| indices.0[i * 8 + j] as i32);
+        let index_reg = _mm256_loadu_si256(std::mem::transmute(index_array.as_ptr()));
+
+        let mask_ptr: *const f32 = std::mem::transmute(mask.0.as_ptr().wrapping_add(i * 8));
+        let mask_reg = _mm256_loadu_ps(mask_ptr);
+
+        let gathered = _mm256_mask_i32gather_ps(_mm256_setzero_ps(), base.as_ptr(), index_reg, mask_reg, 4);
+        _mm256_storeu_ps(result_ptr.wrapping_add(i * 8), gathered);
+    }
+
+    let mut result = result.assume_init();
+    for i in (N/8*8)..N {
+        result.0[i] = if mask.0[i].to_bool() { base[indices.0[i]] } else { 0.0 };
+    }
+    result
+}
+
+impl <const N: usize> Packed<F16, N> {
+
+    /// Widens every lane to `f32`, taking the AVX2 `f16c` path (`vcvtph2ps`)
+    /// when available and falling back to the scalar bit-twiddling
+    /// conversion otherwise.
+    #[inline]
+    pub fn to_f32(&self) -> Packed<f32, N> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("f16c") {
+                unsafe {
+                    return self.to_f32_f16c_impl();
+                }
+            }
+        }
+
+        Packed::from(
+            array![i => self.0[i].to_f32(); N]
+        )
+    }
+
+    #[inline]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[target_feature(enable = "f16c")]
+    unsafe fn to_f32_f16c_impl(&self) -> Packed<f32, N> {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::*;
+
+        let mut result: std::mem::MaybeUninit<Packed<f32, N>> = std::mem::MaybeUninit::uninit();
+        let result_ptr: *mut f32 = std::mem::transmute(result.as_mut_ptr());
+        let half_ptr: *const u16 = std::mem::transmute(self.0.as_ptr());
+
+        for i in 0..(N / 8) {
+            let half_reg = _mm_loadu_si128(half_ptr.wrapping_add(i * 8) as *const __m128i);
+            let float_reg = _mm256_cvtph_ps(half_reg);
+            _mm256_storeu_ps(result_ptr.wrapping_add(i * 8), float_reg);
+        }
+
+        let mut result = result.assume_init();
+        for i in (N / 8 * 8)..N {
+            result.0[i] = self.0[i].to_f32();
+        }
+        result
+    }
+
+    #[inline]
+    pub fn floor(&self) -> Packed<F16, N> { self.to_f32().floor().to_f16() }
+
+    #[inline]
+    pub fn ceil(&self) -> Packed<F16, N> { self.to_f32().ceil().to_f16() }
+
+    #[inline]
+    pub fn round(&self) -> Packed<F16, N> { self.to_f32().round().to_f16() }
+
+    #[inline]
+    pub fn trunc(&self) -> Packed<F16, N> { self.to_f32().trunc().to_f16() }
+
+    #[inline]
+    pub fn fract(&self) -> Packed<F16, N> { self.to_f32().fract().to_f16() }
+
+    #[inline]
+    pub fn abs(&self) -> Packed<F16, N> { self.to_f32().abs().to_f16() }
+
+    #[inline]
+    pub fn recip(&self) -> Packed<F16, N> { self.to_f32().recip().to_f16() }
+
+    #[inline]
+    pub fn powi(&self, n: i32) -> Packed<F16, N> { self.to_f32().powi(n).to_f16() }
+
+    #[inline]
+    pub fn powf(&self, n: F16) -> Packed<F16, N> { self.to_f32().powf(n.to_f32()).to_f16() }
+
+    #[inline]
+    pub fn sqrt(&self) -> Packed<F16, N> { self.to_f32().sqrt().to_f16() }
+
+    #[inline]
+    pub fn exp(&self) -> Packed<F16, N> { self.to_f32().exp().to_f16() }
+
+    #[inline]
+    pub fn exp2(&self) -> Packed<F16, N> { self.to_f32().exp2().to_f16() }
+
+    #[inline]
+    pub fn ln(&self) -> Packed<F16, N> { self.to_f32().ln().to_f16() }
+
+    #[inline]
+    pub fn log(&self, base: F16) -> Packed<F16, N> { self.to_f32().log(base.to_f32()).to_f16() }
+
+    #[inline]
+    pub fn log2(&self) -> Packed<F16, N> { self.to_f32().log2().to_f16() }
+
+    #[inline]
+    pub fn log10(&self) -> Packed<F16, N> { self.to_f32().log10().to_f16() }
+
+    #[inline]
+    pub fn elementwise_max(&self, other: Packed<F16, N>) -> Packed<F16, N> {
+        self.to_f32().elementwise_max(other.to_f32()).to_f16()
+    }
+
+    #[inline]
+    pub fn elementwise_min(&self, other: Packed<F16, N>) -> Packed<F16, N> {
+        self.to_f32().elementwise_min(other.to_f32()).to_f16()
+    }
+}
+
+impl <const N: usize> Packed<f32, N> {
+
+    /// Narrows every lane to `f16`, taking the AVX2 `f16c` path
+    /// (`vcvtps2ph`, round-to-nearest-even) when available and falling back
+    /// to the scalar bit-twiddling conversion otherwise.
+    #[inline]
+    pub fn to_f16(&self) -> Packed<F16, N> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("f16c") {
+                unsafe {
+                    return self.to_f16_f16c_impl();
+                }
+            }
+        }
+
+        Packed::from(
+            array![i => F16::from_f32(self.0[i]); N]
+        )
+    }
+
+    #[inline]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[target_feature(enable = "f16c")]
+    unsafe fn to_f16_f16c_impl(&self) -> Packed<F16, N> {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::*;
+
+        let mut result: std::mem::MaybeUninit<Packed<F16, N>> = std::mem::MaybeUninit::uninit();
+        let half_ptr: *mut u16 = std::mem::transmute(result.as_mut_ptr());
+
+        for i in 0..(N / 8) {
+            let float_ptr: *const f32 = self.0.as_ptr().wrapping_add(i * 8);
+            let float_reg = _mm256_loadu_ps(float_ptr);
+            let half_reg = _mm256_cvtps_ph(float_reg, _MM_FROUND_TO_NEAREST_INT);
+            _mm_storeu_si128(half_ptr.wrapping_add(i * 8) as *mut __m128i, half_reg);
+        }
+
+        let mut result = result.assume_init();
+        for i in (N / 8 * 8)..N {
+            result.0[i] = F16::from_f32(self.0[i]);
+        }
+        result
+    }
+}
+
+impl <const N: usize> ConvertFrom<f64, N> for i32 {
+    #[inline]
+    fn convert_from(value: &Packed<f64, N>, rounding: RoundingMode) -> Packed<i32, N> {
+        let rounded = value.round_with_mode(rounding);
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                unsafe {
+                    return convert_f64_to_i32_avx2(&rounded);
+                }
+            }
+        }
+
+        Packed::from(array![i => rounded.0[i] as i32; N])
+    }
+}
+
+#[inline]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn convert_f64_to_i32_avx2<const N: usize>(rounded: &Packed<f64, N>) -> Packed<i32, N> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    let mut result: std::mem::MaybeUninit<Packed<i32, N>> = std::mem::MaybeUninit::uninit();
+    let result_ptr: *mut i32 = std::mem::transmute(result.as_mut_ptr());
+    let src_ptr: *const f64 = rounded.0.as_ptr();
+
+    let min_val = _mm256_set1_pd(i32::MIN as f64);
+    let max_val = _mm256_set1_pd(i32::MAX as f64);
+
+    for i in 0..(N / 4) {
+        let src_reg = _mm256_loadu_pd(src_ptr.wrapping_add(i * 4));
+        // NaN lanes compare unordered against themselves; zero them out to
+        // match the NaN-to-zero behavior of Rust's saturating `as` cast.
+        let nan_mask = _mm256_cmp_pd(src_reg, src_reg, _CMP_UNORD_Q);
+        let clamped = _mm256_min_pd(_mm256_max_pd(src_reg, min_val), max_val);
+        let safe = _mm256_blendv_pd(clamped, _mm256_setzero_pd(), nan_mask);
+
+        let dst_reg = _mm256_cvttpd_epi32(safe);
+        _mm_storeu_si128(result_ptr.wrapping_add(i * 4) as *mut __m128i, dst_reg);
+    }
+
+    let mut result = result.assume_init();
+    for i in (N / 4 * 4)..N {
+        result.0[i] = rounded.0[i] as i32;
+    }
+    result
+}
+
+impl <const N: usize> ConvertFrom<i32, N> for f64 {
+    #[inline]
+    fn convert_from(value: &Packed<i32, N>, _rounding: RoundingMode) -> Packed<f64, N> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                unsafe {
+                    return convert_i32_to_f64_avx2(value);
+                }
+            }
+        }
+
+        Packed::from(array![i => value.0[i] as f64; N])
+    }
+}
+
+#[inline]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn convert_i32_to_f64_avx2<const N: usize>(value: &Packed<i32, N>) -> Packed<f64, N> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    let mut result: std::mem::MaybeUninit<Packed<f64, N>> = std::mem::MaybeUninit::uninit();
+    let result_ptr: *mut f64 = std::mem::transmute(result.as_mut_ptr());
+    let src_ptr: *const i32 = value.0.as_ptr();
+
+    for i in 0..(N / 4) {
+        let src_reg = _mm_loadu_si128(src_ptr.wrapping_add(i * 4) as *const __m128i);
+        let dst_reg = _mm256_cvtepi32_pd(src_reg);
+        _mm256_storeu_pd(result_ptr.wrapping_add(i * 4), dst_reg);
+    }
+
+    let mut result = result.assume_init();
+    for i in (N / 4 * 4)..N {
+        result.0[i] = value.0[i] as f64;
+    }
+    result
+}
+
+impl <const N: usize> ConvertFrom<f32, N> for i32 {
+    #[inline]
+    fn convert_from(value: &Packed<f32, N>, rounding: RoundingMode) -> Packed<i32, N> {
+        let rounded = value.round_with_mode(rounding);
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                unsafe {
+                    return convert_f32_to_i32_avx2(&rounded);
+                }
+            }
+        }
+
+        Packed::from(array![i => rounded.0[i] as i32; N])
+    }
+}
+
+#[inline]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn convert_f32_to_i32_avx2<const N: usize>(rounded: &Packed<f32, N>) -> Packed<i32, N> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    let mut result: std::mem::MaybeUninit<Packed<i32, N>> = std::mem::MaybeUninit::uninit();
+    let result_ptr: *mut i32 = std::mem::transmute(result.as_mut_ptr());
+    let src_ptr: *const f32 = rounded.0.as_ptr();
+
+    let min_val = _mm256_set1_ps(i32::MIN as f32);
+    let max_val = _mm256_set1_ps(i32::MAX as f32);
+
+    for i in 0..(N / 8) {
+        let src_reg = _mm256_loadu_ps(src_ptr.wrapping_add(i * 8));
+        let nan_mask = _mm256_cmp_ps(src_reg, src_reg, _CMP_UNORD_Q);
+        let clamped = _mm256_min_ps(_mm256_max_ps(src_reg, min_val), max_val);
+        let safe = _mm256_blendv_ps(clamped, _mm256_setzero_ps(), nan_mask);
+
+        let dst_reg = _mm256_cvttps_epi32(safe);
+        _mm256_storeu_si256(result_ptr.wrapping_add(i * 8) as *mut __m256i, dst_reg);
+    }
+
+    let mut result = result.assume_init();
+    for i in (N / 8 * 8)..N {
+        result.0[i] = rounded.0[i] as i32;
+    }
+    result
+}
+
+impl <const N: usize> ConvertFrom<i32, N> for f32 {
+    #[inline]
+    fn convert_from(value: &Packed<i32, N>, _rounding: RoundingMode) -> Packed<f32, N> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                unsafe {
+                    return convert_i32_to_f32_avx2(value);
+                }
+            }
+        }
+
+        Packed::from(array![i => value.0[i] as f32; N])
+    }
+}
+
+#[inline]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn convert_i32_to_f32_avx2<const N: usize>(value: &Packed<i32, N>) -> Packed<f32, N> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    let mut result: std::mem::MaybeUninit<Packed<f32, N>> = std::mem::MaybeUninit::uninit();
+    let result_ptr: *mut f32 = std::mem::transmute(result.as_mut_ptr());
+    let src_ptr: *const i32 = value.0.as_ptr();
+
+    for i in 0..(N / 8) {
+        let src_reg = _mm256_loadu_si256(src_ptr.wrapping_add(i * 8) as *const __m256i);
+        let dst_reg = _mm256_cvtepi32_ps(src_reg);
+        _mm256_storeu_ps(result_ptr.wrapping_add(i * 8), dst_reg);
+    }
+
+    let mut result = result.assume_init();
+    for i in (N / 8 * 8)..N {
+        result.0[i] = value.0[i] as f32;
+    }
+    result
+}
+
+impl <const N: usize> ConvertFrom<f64, N> for f32 {
+    #[inline]
+    fn convert_from(value: &Packed<f64, N>, _rounding: RoundingMode) -> Packed<f32, N> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                unsafe {
+                    return convert_f64_to_f32_avx2(value);
+                }
+            }
+        }
+
+        Packed::from(array![i => value.0[i] as f32; N])
+    }
+}
+
+#[inline]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn convert_f64_to_f32_avx2<const N: usize>(value: &Packed<f64, N>) -> Packed<f32, N> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    let mut result: std::mem::MaybeUninit<Packed<f32, N>> = std::mem::MaybeUninit::uninit();
+    // Each `_mm256_cvtpd_ps` narrows 4 lanes (one `f64` register) down to a
+    // 128-bit `f32` register, so results are written 4 lanes at a time.
+    let result_ptr: *mut f32 = std::mem::transmute(result.as_mut_ptr());
+    let src_ptr: *const f64 = value.0.as_ptr();
+
+    for i in 0..(N / 4) {
+        let src_reg = _mm256_loadu_pd(src_ptr.wrapping_add(i * 4));
+        let dst_reg = _mm256_cvtpd_ps(src_reg);
+        _mm_storeu_ps(result_ptr.wrapping_add(i * 4), dst_reg);
+    }
+
+    let mut result = result.assume_init();
+    for i in (N / 4 * 4)..N {
+        result.0[i] = value.0[i] as f32;
+    }
+    result
+}
+
+impl <const N: usize> ConvertFrom<f32, N> for f64 {
+    #[inline]
+    fn convert_from(value: &Packed<f32, N>, _rounding: RoundingMode) -> Packed<f64, N> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                unsafe {
+                    return convert_f32_to_f64_avx2(value);
+                }
+            }
+        }
+
+        Packed::from(array![i => value.0[i] as f64; N])
+    }
+}
+
+#[inline]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn convert_f32_to_f64_avx2<const N: usize>(value: &Packed<f32, N>) -> Packed<f64, N> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    let mut result: std::mem::MaybeUninit<Packed<f64, N>> = std::mem::MaybeUninit::uninit();
+    let result_ptr: *mut f64 = std::mem::transmute(result.as_mut_ptr());
+    let src_ptr: *const f32 = value.0.as_ptr();
+
+    for i in 0..(N / 4) {
+        let src_reg = _mm_loadu_ps(src_ptr.wrapping_add(i * 4));
+        let dst_reg = _mm256_cvtps_pd(src_reg);
+        _mm256_storeu_pd(result_ptr.wrapping_add(i * 4), dst_reg);
+    }
+
+    let mut result = result.assume_init();
+    for i in (N / 4 * 4)..N {
+        result.0[i] = value.0[i] as f64;
+    }
+    result
+}
+/// SIMD instruction set `Packed`'s type-specific fast paths (e.g.
+/// `assign_masked_f64`, `gather_f32`) can take advantage of on this CPU, and
+/// the native lane count that goes with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Scalar,
+    Avx2,
+}
+
+impl Backend {
+    #[inline]
+    pub fn f64_lanes(&self) -> usize {
+        match self {
+            Backend::Scalar => 1,
+            Backend::Avx2 => 4,
+        }
+    }
+
+    #[inline]
+    pub fn f32_lanes(&self) -> usize {
+        match self {
+            Backend::Scalar => 1,
+            Backend::Avx2 => 8,
+        }
+    }
+}
+
+/// Probes CPU features once and reports the widest backend available, so
+/// renderer code can size its `Packed<T, N>` batches to the hardware
+/// instead of each hot method re-running `is_x86_feature_detected!`
+/// individually. True function multiversioning — recompiling a generic
+/// kernel per backend so call sites need no runtime branch at all — would
+/// need nightly `#[target_feature]`-on-safe-fn or an external
+/// multiversioning crate, neither available in this tree; this is the
+/// runtime-detection half of that design. The actual speedup still comes
+/// from the per-type fast paths `Packed` implements (`assign_masked_f64`,
+/// `gather_f64`, `to_f32`/`to_f16`, ...), which `dispatch()` lets callers
+/// pick a lane count for instead of guessing.
+#[inline]
+pub fn dispatch() -> Backend {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx2") {
+            return Backend::Avx2;
+        }
+    }
+
+    Backend::Scalar
+}
+
+/// Facade over the arithmetic/compare/mask/select/reduce operations
+/// `Packed<T, N>` already exposes as inherent methods and operators, so
+/// renderer code can be written once against `SimdBackend` rather than
+/// calling into `Packed` directly — the same generic-kernel-over-backends
+/// shape the vector-math IR uses. `PackedBackend` is the only implementor:
+/// it forwards every call straight through to `Packed`'s own operators and
+/// methods, which already contain whatever feature-gated fast path exists
+/// for that operation.
+pub trait SimdBackend<T: Scaler, const N: usize> {
+    fn add(a: Packed<T, N>, b: Packed<T, N>) -> Packed<T, N>
+    where T: std::ops::Add<T, Output = T>;
+
+    fn sub(a: Packed<T, N>, b: Packed<T, N>) -> Packed<T, N>
+    where T: std::ops::Sub<T, Output = T>;
+
+    fn mul(a: Packed<T, N>, b: Packed<T, N>) -> Packed<T, N>
+    where T: std::ops::Mul<T, Output = T>;
+
+    fn div(a: Packed<T, N>, b: Packed<T, N>) -> Packed<T, N>
+    where T: std::ops::Div<T, Output = T>;
+
+    fn select_masked<M>(base: Packed<T, N>, other: Packed<T, N>, mask: Packed<M, N>) -> Packed<T, N>
+    where T: Scaler<MaskType = M>, M: Mask;
+
+    fn sum(a: Packed<T, N>) -> T
+    where T: std::ops::Add<T, Output = T>;
+
+    fn elementwise_min(a: Packed<T, N>, b: Packed<T, N>) -> Packed<T, N>
+    where T: Float;
+
+    fn elementwise_max(a: Packed<T, N>, b: Packed<T, N>) -> Packed<T, N>
+    where T: Float;
+}
+
+pub struct PackedBackend;
+
+impl <T: Scaler, const N: usize> SimdBackend<T, N> for PackedBackend {
+    #[inline]
+    fn add(a: Packed<T, N>, b: Packed<T, N>) -> Packed<T, N>
+    where T: std::ops::Add<T, Output = T>
+    {
+        a + b
+    }
+
+    #[inline]
+    fn sub(a: Packed<T, N>, b: Packed<T, N>) -> Packed<T, N>
+    where T: std::ops::Sub<T, Output = T>
+    {
+        a - b
+    }
+
+    #[inline]
+    fn mul(a: Packed<T, N>, b: Packed<T, N>) -> Packed<T, N>
+    where T: std::ops::Mul<T, Output = T>
+    {
+        a * b
+    }
+
+    #[inline]
+    fn div(a: Packed<T, N>, b: Packed<T, N>) -> Packed<T, N>
+    where T: std::ops::Div<T, Output = T>
+    {
+        a / b
+    }
+
+    #[inline]
+    fn select_masked<M>(base: Packed<T, N>, other: Packed<T, N>, mask: Packed<M, N>) -> Packed<T, N>
+    where T: Scaler<MaskType = M>, M: Mask
+    {
+        base.select_masked(other, mask)
+    }
+
+    #[inline]
+    fn sum(a: Packed<T, N>) -> T
+    where T: std::ops::Add<T, Output = T>
+    {
+        a.sum()
+    }
+
+    #[inline]
+    fn elementwise_min(a: Packed<T, N>, b: Packed<T, N>) -> Packed<T, N>
+    where T: Float
+    {
+        a.elementwise_min(b)
+    }
+
+    #[inline]
+    fn elementwise_max(a: Packed<T, N>, b: Packed<T, N>) -> Packed<T, N>
+    where T: Float
+    {
+        a.elementwise_max(b)
+    }
+}
+
+/// Fixed-size `R x C` matrix stored as `R` `Packed<T, C>` rows, for the
+/// small 3x3/4x4 transforms ray tracing needs (camera basis, instancing,
+/// normal matrices) without pulling in a general-purpose linear algebra
+/// crate.
+#[derive(Copy, Clone, Debug)]
+pub struct Matrix<T: Scaler, const R: usize, const C: usize>(
+    [Packed<T, C>; R]
+);
+
+impl <T: Scaler, const R: usize, const C: usize> Matrix<T, R, C> {
+    #[inline]
+    pub fn from_rows(rows: [Packed<T, C>; R]) -> Matrix<T, R, C> {
+        Matrix(rows)
+    }
+
+    #[inline]
+    pub fn row(&self, index: usize) -> Packed<T, C> {
+        self.0[index]
+    }
+
+    #[inline]
+    pub fn rows(&self) -> &[Packed<T, C>; R] {
+        &self.0
+    }
+}
+
+impl <T, const R: usize, const C: usize> Matrix<T, R, C>
+where T: Scaler + Num
+{
+    #[inline]
+    pub fn zero() -> Matrix<T, R, C> {
+        Matrix(array![Packed::<T, C>::broadcast_scaler(T::zero()); R])
+    }
+
+    #[inline]
+    pub fn transpose(&self) -> Matrix<T, C, R> {
+        Matrix(
+            array![j => Packed::<T, R>::from(array![i => self.0[i][j]; R]); C]
+        )
+    }
+
+    /// Row-vectorized multiply: each output row is built by broadcasting
+    /// one source element at a time and accumulating it times the matching
+    /// row of `other`, reusing `Packed`'s own `Add`/`Mul` lane ops instead
+    /// of a per-element scalar triple loop.
+    #[inline]
+    pub fn matmul<const C2: usize>(&self, other: &Matrix<T, C, C2>) -> Matrix<T, R, C2> {
+        Matrix(
+            array![i => {
+                let mut acc = Packed::<T, C2>::broadcast_scaler(T::zero());
+                for k in 0..C {
+                    acc = acc + Packed::<T, C2>::broadcast_scaler(self.0[i][k]) * other.0[k];
+                }
+                acc
+            }; R]
+        )
+    }
+}
+
+impl <T, const N: usize> Matrix<T, N, N>
+where T: Scaler + Num
+{
+    #[inline]
+    pub fn identity() -> Matrix<T, N, N> {
+        Matrix(
+            array![i => Packed::<T, N>::from(array![j => if i == j { T::one() } else { T::zero() }; N]); N]
+        )
+    }
+
+    /// `self` raised to the `n`-th power by square-and-multiply, so
+    /// composing a transform `n` times (or building a geometric series of
+    /// transforms) costs `O(log n)` matrix multiplies instead of `n`.
+    pub fn matrix_pow(&self, mut n: u64) -> Matrix<T, N, N> {
+        let mut result = Matrix::<T, N, N>::identity();
+        let mut base = *self;
+
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.matmul(&base);
+            }
+            base = base.matmul(&base);
+            n >>= 1;
+        }
+
+        result
+    }
+}
@@ -0,0 +1,150 @@
+use image::Rgb;
+
+/// How many representative colors `quantize_block` reduces a tile to.
+/// `Four` is obtained by splitting each of `Two`'s clusters once more.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockColorCount {
+    One,
+    Two,
+    Four,
+}
+
+/// Knobs for `quantize_block`, threaded through `TileRenderer` so every block
+/// in a render is reduced the same way.
+#[derive(Debug, Clone, Copy)]
+pub struct QuantizeConfig {
+    pub block_color_count: BlockColorCount,
+    /// Blocks whose variance (mean over the block's pixels of the squared RGB
+    /// distance, summed over the three channels, to the block's own mean
+    /// color) falls below this are emitted
+    /// as a single flat color regardless of `block_color_count`.
+    pub skip_threshold: f64,
+}
+
+/// A block reduced to up to four representative colors plus one index per
+/// pixel (row-major within the block) selecting which color it uses.
+#[derive(Debug, Clone)]
+pub struct BlockPalette {
+    pub colors: Vec<Rgb<u8>>,
+    pub indices: Vec<u8>,
+}
+
+impl BlockPalette {
+    /// Expands the palette + indices back into a full pixel buffer, the same
+    /// size and order as the block it was quantized from.
+    pub fn to_pixels(&self) -> Vec<Rgb<u8>> {
+        self.indices.iter().map(|&i| self.colors[i as usize]).collect()
+    }
+}
+
+fn squared_distance(a: &Rgb<u8>, b: &Rgb<u8>) -> f64 {
+    (0..3).map(|c| {
+        let diff = a[c] as f64 - b[c] as f64;
+        diff * diff
+    }).sum()
+}
+
+fn mean_color(pixels: &[Rgb<u8>]) -> Rgb<u8> {
+    let mut sum = [0f64; 3];
+    for pixel in pixels {
+        for c in 0..3 {
+            sum[c] += pixel[c] as f64;
+        }
+    }
+
+    Rgb(std::array::from_fn(|c| (sum[c] / pixels.len() as f64).round() as u8))
+}
+
+fn luma(pixel: &Rgb<u8>) -> f64 {
+    0.2126 * pixel[0] as f64 + 0.7152 * pixel[1] as f64 + 0.0722 * pixel[2] as f64
+}
+
+fn variance(pixels: &[Rgb<u8>], mean: &Rgb<u8>) -> f64 {
+    pixels.iter().map(|p| squared_distance(p, mean)).sum::<f64>() / pixels.len() as f64
+}
+
+fn nearest_centroid(pixel: &Rgb<u8>, centroids: &[Rgb<u8>]) -> u8 {
+    centroids.iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| squared_distance(pixel, a).partial_cmp(&squared_distance(pixel, b)).unwrap())
+        .map(|(index, _)| index as u8)
+        .unwrap()
+}
+
+/// Splits `pixels` into two clusters, seeded by a dark/bright split relative
+/// to the block's mean luma, then refines the two centroids `iterations`
+/// times by re-assigning pixels to their nearer centroid and recomputing
+/// means. Returns the two centroids and each pixel's cluster (0 or 1).
+fn two_means(pixels: &[Rgb<u8>], iterations: usize) -> (Rgb<u8>, Rgb<u8>, Vec<u8>) {
+    let mean = mean_color(pixels);
+    let mean_luma = luma(&mean);
+
+    let mut assignment: Vec<u8> = pixels.iter().map(|p| if luma(p) >= mean_luma { 1 } else { 0 }).collect();
+    let mut centroids = [mean, mean];
+
+    for _ in 0..=iterations {
+        for cluster in 0..2 {
+            let cluster_pixels: Vec<Rgb<u8>> = pixels.iter().zip(&assignment)
+                .filter(|(_, &a)| a as usize == cluster)
+                .map(|(p, _)| *p)
+                .collect();
+
+            if !cluster_pixels.is_empty() {
+                centroids[cluster] = mean_color(&cluster_pixels);
+            }
+        }
+
+        assignment = pixels.iter().map(|p| nearest_centroid(p, &centroids)).collect();
+    }
+
+    (centroids[0], centroids[1], assignment)
+}
+
+/// Reduces one tile's pixels to a small per-block palette: computes the
+/// block's mean color and variance, emits a flat block below
+/// `config.skip_threshold`, otherwise clusters into `block_color_count`
+/// representative colors (via `two_means`, split again for `Four`) and
+/// assigns every pixel to its nearest one.
+pub fn quantize_block(pixels: &[Rgb<u8>], config: &QuantizeConfig) -> BlockPalette {
+    let mean = mean_color(pixels);
+
+    if variance(pixels, &mean) < config.skip_threshold {
+        return BlockPalette { colors: vec![mean], indices: vec![0; pixels.len()] };
+    }
+
+    match config.block_color_count {
+        BlockColorCount::One => BlockPalette { colors: vec![mean], indices: vec![0; pixels.len()] },
+        BlockColorCount::Two => {
+            let (first, second, indices) = two_means(pixels, 2);
+            BlockPalette { colors: vec![first, second], indices }
+        },
+        BlockColorCount::Four => {
+            let (first_centroid, second_centroid, split) = two_means(pixels, 2);
+
+            let first_group: Vec<Rgb<u8>> = pixels.iter().zip(&split).filter(|(_, &a)| a == 0).map(|(p, _)| *p).collect();
+            let second_group: Vec<Rgb<u8>> = pixels.iter().zip(&split).filter(|(_, &a)| a == 1).map(|(p, _)| *p).collect();
+
+            // A degenerate luma split can leave one side empty; re-splitting
+            // an empty slice would divide by zero in `mean_color`, so we keep
+            // that side's single outer centroid instead of feeding `two_means`
+            // nothing to cluster.
+            let first_colors = if first_group.is_empty() {
+                vec![first_centroid]
+            } else {
+                let (first_a, first_b, _) = two_means(&first_group, 2);
+                vec![first_a, first_b]
+            };
+            let second_colors = if second_group.is_empty() {
+                vec![second_centroid]
+            } else {
+                let (second_a, second_b, _) = two_means(&second_group, 2);
+                vec![second_a, second_b]
+            };
+
+            let colors: Vec<Rgb<u8>> = first_colors.into_iter().chain(second_colors).collect();
+            let indices = pixels.iter().map(|p| nearest_centroid(p, &colors)).collect();
+
+            BlockPalette { colors, indices }
+        },
+    }
+}
@@ -6,7 +6,8 @@ use crate::{geometry::{Vec3, Point3, PackedVec3, PackedPoint3}, simd_util::SimdP
 #[derive(Clone, Copy)]
 pub struct Ray {
     origin: Point3,
-    direction: Vec3
+    direction: Vec3,
+    time: f64,
 }
 
 impl Ray {
@@ -14,9 +15,20 @@ impl Ray {
         Ray {
             origin: origin,
             direction: direction,
+            time: 0.0,
         }
     }
-    
+
+    /// For motion blur: `time` is when, within the camera's shutter interval,
+    /// this ray was cast, used to evaluate moving objects at the right position.
+    pub fn new_with_time(origin: Point3, direction: Vec3, time: f64) -> Ray{
+        Ray {
+            origin: origin,
+            direction: direction,
+            time: time,
+        }
+    }
+
     pub fn origin(&self) -> Point3 {
         self.origin
     }
@@ -25,6 +37,10 @@ impl Ray {
         self.direction
     }
 
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
     pub fn at(&self, t: f64) -> Point3 {
         self.origin + self.direction * t
     }
@@ -33,16 +49,17 @@ impl Ray {
 
 #[derive(Debug)]
 #[derive(Copy, Clone)]
-pub struct PackedRays<const N: usize> 
+pub struct PackedRays<const N: usize>
 where
     LaneCount<N>: SupportedLaneCount,
 {
     origins: PackedPoint3<N>,
     directions: PackedVec3<N>,
-    enabled: Mask<<f64 as SimdElement>::Mask, N>
+    enabled: Mask<<f64 as SimdElement>::Mask, N>,
+    time: Simd<f64, N>,
 }
 
-impl <const N: usize> PackedRays<N> 
+impl <const N: usize> PackedRays<N>
 where LaneCount<N>: SupportedLaneCount
 {
     #[inline]
@@ -50,13 +67,34 @@ where LaneCount<N>: SupportedLaneCount
         PackedRays {
             origins,
             directions,
-            enabled: Mask::splat(true)
+            enabled: Mask::splat(true),
+            time: Simd::splat(0.0),
+        }
+    }
+
+    /// For motion blur: each lane's `time` is when, within the camera's
+    /// shutter interval, that ray was cast (see `Ray::new_with_time`).
+    #[inline]
+    pub fn new_with_time(origins: PackedPoint3<N>, directions: PackedVec3<N>, time: Simd<f64, N>) -> PackedRays<N> {
+        PackedRays {
+            origins,
+            directions,
+            enabled: Mask::splat(true),
+            time,
         }
     }
 
     #[inline]
     pub fn new_with_enable(origins: PackedPoint3<N>, directions: PackedVec3<N>, enabled: Mask<<f64 as SimdElement>::Mask, N>) -> PackedRays<N> {
-        PackedRays { origins, directions, enabled }
+        PackedRays { origins, directions, enabled, time: Simd::splat(0.0) }
+    }
+
+    /// Like `new_with_enable`, but also carries forward each lane's time --
+    /// used when narrowing/transforming an existing `PackedRays` (BVH descent,
+    /// `Instance` local-space rays) so motion blur survives the narrowing.
+    #[inline]
+    pub fn new_with_enable_and_time(origins: PackedPoint3<N>, directions: PackedVec3<N>, enabled: Mask<<f64 as SimdElement>::Mask, N>, time: Simd<f64, N>) -> PackedRays<N> {
+        PackedRays { origins, directions, enabled, time }
     }
 
     #[inline]
@@ -69,6 +107,11 @@ where LaneCount<N>: SupportedLaneCount
         self.directions
     }
 
+    #[inline]
+    pub fn time(&self) -> Simd<f64, N> {
+        self.time
+    }
+
     #[inline]
     pub fn enabled(&self) -> Mask<<f64 as SimdElement>::Mask, N> {
         self.enabled
@@ -87,7 +130,7 @@ where LaneCount<N>: SupportedLaneCount
     #[inline]
     pub fn at(&self, index: usize) -> Option<Ray> {
         if self.enabled.test(index) {
-            Some(Ray::new(self.origins.at(index), self.directions.at(index)))
+            Some(Ray::new_with_time(self.origins.at(index), self.directions.at(index), self.time[index]))
         } else {
             None
         }
@@ -95,7 +138,7 @@ where LaneCount<N>: SupportedLaneCount
 
     #[inline]
     pub fn at_including_disabled(&self, index: usize) -> Ray {
-        Ray::new(self.origins.at(index), self.directions.at(index))
+        Ray::new_with_time(self.origins.at(index), self.directions.at(index), self.time[index])
     }
 
     #[inline]
@@ -108,6 +151,7 @@ where LaneCount<N>: SupportedLaneCount
         self.origins.update(index, value.origin());
         self.directions.update(index, value.direction());
         self.enabled.set(index, true);
+        self.time[index] = value.time();
     }
 
     #[inline]
@@ -115,6 +159,7 @@ where LaneCount<N>: SupportedLaneCount
         self.origins.update(index, value.origin());
         self.directions.update(index, value.direction());
         self.enabled.set(index, enable);
+        self.time[index] = value.time();
     }
 
     #[inline]
@@ -140,7 +185,8 @@ where LaneCount<N>: SupportedLaneCount
         let mut packed_rays = PackedRays {
             directions: PackedVec3::default(),
             origins: PackedPoint3::default(),
-            enabled: Mask::splat(false)
+            enabled: Mask::splat(false),
+            time: Simd::splat(0.0),
         };
 
         for (index, value) in iter.into_iter().enumerate() {
@@ -160,25 +206,27 @@ where
     fn permute(&mut self, tmp_buffer: Self, chunk_indices: &[Simd<usize, N>], lane_indices: &[Simd<usize, N>]) {
         unsafe {
             tmp_buffer.copy_from_slice(self);
-            let temp_as_slice: &[f64] = std::slice::from_raw_parts(std::mem::transmute(tmp_buffer.as_ptr()), self.len() * N * 7);
-            let temp_as_mask_slice: &[<f64 as SimdElement>::Mask] = std::slice::from_raw_parts(std::mem::transmute(tmp_buffer.as_ptr()), self.len() * N * 7);
-            
+            let temp_as_slice: &[f64] = std::slice::from_raw_parts(std::mem::transmute(tmp_buffer.as_ptr()), self.len() * N * 8);
+            let temp_as_mask_slice: &[<f64 as SimdElement>::Mask] = std::slice::from_raw_parts(std::mem::transmute(tmp_buffer.as_ptr()), self.len() * N * 8);
+
             for i in 0..self.len() {
                 self[i].origins = PackedVec3::from_simd(
-                    Simd::gather_or_default(temp_as_slice, chunk_indices[i] * Simd::splat(N * 7) + lane_indices[i]),
-                    Simd::gather_or_default(temp_as_slice, chunk_indices[i] * Simd::splat(N * 7) + Simd::splat(N) + lane_indices[i]),
-                    Simd::gather_or_default(temp_as_slice, chunk_indices[i] * Simd::splat(N * 7) + Simd::splat(2 * N) + lane_indices[i])
+                    Simd::gather_or_default(temp_as_slice, chunk_indices[i] * Simd::splat(N * 8) + lane_indices[i]),
+                    Simd::gather_or_default(temp_as_slice, chunk_indices[i] * Simd::splat(N * 8) + Simd::splat(N) + lane_indices[i]),
+                    Simd::gather_or_default(temp_as_slice, chunk_indices[i] * Simd::splat(N * 8) + Simd::splat(2 * N) + lane_indices[i])
                 );
 
                 self[i].directions = PackedVec3::from_simd(
-                    Simd::gather_or_default(temp_as_slice, chunk_indices[i] * Simd::splat(N * 7) + Simd::splat(3 * N) + lane_indices[i]),
-                    Simd::gather_or_default(temp_as_slice, chunk_indices[i] * Simd::splat(N * 7) + Simd::splat(4 * N) + lane_indices[i]),
-                    Simd::gather_or_default(temp_as_slice, chunk_indices[i] * Simd::splat(N * 7) + Simd::splat(5 * N) + lane_indices[i])
+                    Simd::gather_or_default(temp_as_slice, chunk_indices[i] * Simd::splat(N * 8) + Simd::splat(3 * N) + lane_indices[i]),
+                    Simd::gather_or_default(temp_as_slice, chunk_indices[i] * Simd::splat(N * 8) + Simd::splat(4 * N) + lane_indices[i]),
+                    Simd::gather_or_default(temp_as_slice, chunk_indices[i] * Simd::splat(N * 8) + Simd::splat(5 * N) + lane_indices[i])
                 );
 
                 self[i].enabled = std::mem::transmute_copy(
-                    &Simd::gather_or_default(temp_as_mask_slice, chunk_indices[i] * Simd::splat(N * 7) + Simd::splat(6 * N) + lane_indices[i])
-                )
+                    &Simd::gather_or_default(temp_as_mask_slice, chunk_indices[i] * Simd::splat(N * 8) + Simd::splat(6 * N) + lane_indices[i])
+                );
+
+                self[i].time = Simd::gather_or_default(temp_as_slice, chunk_indices[i] * Simd::splat(N * 8) + Simd::splat(7 * N) + lane_indices[i]);
             }
         }
     }
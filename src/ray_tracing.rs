@@ -2,13 +2,29 @@ use crate::color::PackedColor;
 use crate::packed::{PackedF64Mask, Scaler, Mask};
 use crate::{color::Color, objects::Object};
 use crate::objects::{HitRecord, PackedHitRecords, HitResult};
+use crate::bvh::Bvh;
+use crate::light::Light;
 use crate::geometry::{Vec3, Point3, PackedVec3, PackedPoint3};
 use crate::ray::{Ray, PackedRays};
 
 use std::fmt::Debug;
+use std::simd::{Simd, LaneCount, SupportedLaneCount};
 use rand::prelude::*;
 use array_macro::array;
 
+/// How `get_ray` maps a pixel's normalized image coordinates to a ray
+/// direction. `Perspective` is the crate's original planar viewport;
+/// the rest trade the viewport plane for an angular mapping so a single
+/// camera can cover a full 360-degree field of view or a distorted lens.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ProjectionMode {
+    Perspective,
+    Orthographic,
+    Equirectangular,
+    Fisheye,
+}
+
 #[derive(Debug)]
 #[derive(Clone)]
 pub struct Camera {
@@ -20,10 +36,19 @@ pub struct Camera {
     viewport_v: Vec3,
     defocus_u: Vec3,
     defocus_v: Vec3,
+    shutter_open: f64,
+    shutter_close: f64,
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+    view_angle: f64,
+    projection: ProjectionMode,
+    focal_length: f64,
+    up: Vec3,
 }
 
 impl Camera {
-    pub fn new(image_width: usize, image_height: usize, focal_length: f64, view_angle: f64, center: Point3, look_at: Vec3, up: Vec3, defocus_angle: f64) -> Camera {
+    pub fn new(image_width: usize, image_height: usize, focal_length: f64, view_angle: f64, center: Point3, look_at: Vec3, up: Vec3, defocus_angle: f64, shutter_open: f64, shutter_close: f64, projection: ProjectionMode) -> Camera {
         let aspect_ratio = (image_width as f64) / (image_height as f64);
         let diagonal_length = (view_angle.to_radians() / 2.0).tan() * focal_length * 2.0;
         let upper_left_diagonal_angle = aspect_ratio.atan();
@@ -57,6 +82,15 @@ impl Camera {
             viewport_v,
             defocus_u,
             defocus_v,
+            shutter_open,
+            shutter_close,
+            u,
+            v,
+            w,
+            view_angle,
+            projection,
+            focal_length,
+            up,
         }
     }
 
@@ -69,22 +103,92 @@ impl Camera {
         let look_at =  Vec3::from_toml(&table["look_at"]).unwrap();
         let up = Vec3::from_toml(&table["up"]).unwrap();
         let defocus_angle = table["view_angle"].as_float().unwrap();
+        let shutter_open = table.get("shutter_open").and_then(|v| v.as_float()).unwrap_or(0.0);
+        let shutter_close = table.get("shutter_close").and_then(|v| v.as_float()).unwrap_or(0.0);
+        let projection = match table.get("projection").and_then(|v| v.as_str()) {
+            Some("orthographic") => ProjectionMode::Orthographic,
+            Some("equirectangular") => ProjectionMode::Equirectangular,
+            Some("fisheye") => ProjectionMode::Fisheye,
+            _ => ProjectionMode::Perspective,
+        };
+
+        Camera::new(image_width, image_height, focal_length, view_angle, center, look_at, up, defocus_angle, shutter_open, shutter_close, projection)
+    }
 
-        Camera::new(image_width, image_height, focal_length, view_angle, center, look_at, up, defocus_angle)
+    /// Builds the ray origin and direction for a pixel's normalized image
+    /// coordinates `u_norm`/`v_norm` (each ranging from 0 up to, but not
+    /// including, 1, with `(0, 0)` at the top left), according to
+    /// `self.projection`. `disk_offset` is the defocus sample; every mode
+    /// offsets the ray origin by it, but only `Perspective` lets it change
+    /// the ray's direction.
+    fn project(&self, u_norm: f64, v_norm: f64, disk_offset: Vec3) -> (Point3, Vec3) {
+        let defocus_offset = self.defocus_u * disk_offset.x() + self.defocus_v * disk_offset.y();
+
+        match self.projection {
+            ProjectionMode::Perspective => {
+                let pixel_offset = self.viewport_u * u_norm + self.viewport_v * v_norm;
+                let pixel_center = self.viewport_upper_left_corner + pixel_offset;
+                let ray_origin = defocus_offset + self.center;
+                (ray_origin, (pixel_center - ray_origin).unit())
+            },
+            ProjectionMode::Orthographic => {
+                let pixel_offset = self.viewport_u * u_norm + self.viewport_v * v_norm;
+                let pixel_center = self.viewport_upper_left_corner + pixel_offset;
+                (defocus_offset + pixel_center, -self.w)
+            },
+            ProjectionMode::Equirectangular => {
+                let phi = (u_norm - 0.5) * 2.0 * std::f64::consts::PI;
+                let theta = (0.5 - v_norm) * std::f64::consts::PI;
+                let local_direction = Vec3::new(theta.cos() * phi.sin(), theta.sin(), -theta.cos() * phi.cos());
+                let ray_direction = self.u * local_direction.x() + self.v * local_direction.y() + self.w * local_direction.z();
+                (defocus_offset + self.center, ray_direction.unit())
+            },
+            ProjectionMode::Fisheye => {
+                let dx = u_norm * 2.0 - 1.0;
+                let dy = v_norm * 2.0 - 1.0;
+                let radius = (dx * dx + dy * dy).sqrt().min(1.0);
+                let angle = radius * self.view_angle.to_radians() / 2.0;
+                let azimuth = dy.atan2(dx);
+                let local_direction = Vec3::new(angle.sin() * azimuth.cos(), angle.sin() * azimuth.sin(), -angle.cos());
+                let ray_direction = self.u * local_direction.x() + self.v * local_direction.y() + self.w * local_direction.z();
+                (defocus_offset + self.center, ray_direction.unit())
+            },
+        }
     }
 
     pub fn get_ray(&self, col: usize,  row: usize) -> Ray {
         let x_offset = thread_rng().gen_range(0.0..1.0);
         let y_offset = thread_rng().gen_range(0.0..1.0);
-        let pixel_offset = self.viewport_u * ((col as f64 + x_offset) / (self.image_width as f64)) + self.viewport_v * ((row as f64 + y_offset) / (self.image_height as f64));
-        let pixel_center = self.viewport_upper_left_corner + pixel_offset;
+        let u_norm = (col as f64 + x_offset) / (self.image_width as f64);
+        let v_norm = (row as f64 + y_offset) / (self.image_height as f64);
+        let disk_offset = Vec3::random_in_unit_disk();
+        let (ray_origin, ray_direction) = self.project(u_norm, v_norm, disk_offset);
+        let time = if self.shutter_close > self.shutter_open {
+            thread_rng().gen_range(self.shutter_open..self.shutter_close)
+        } else {
+            self.shutter_open
+        };
+
+        Ray::new_with_time(ray_origin, ray_direction, time)
+    }
+
+    /// Like `get_ray`, but the subpixel offset is drawn from `[-radius, radius]`
+    /// instead of a fixed half-pixel box, and is returned alongside the ray so
+    /// a `Filter` can weight this sample relative to the pixel center.
+    pub fn get_ray_with_offset(&self, col: usize, row: usize, radius: f64) -> (Ray, f64, f64) {
+        let dx = thread_rng().gen_range(-radius..=radius);
+        let dy = thread_rng().gen_range(-radius..=radius);
+        let u_norm = (col as f64 + 0.5 + dx) / (self.image_width as f64);
+        let v_norm = (row as f64 + 0.5 + dy) / (self.image_height as f64);
         let disk_offset = Vec3::random_in_unit_disk();
-        let ray_origin = self.defocus_u * disk_offset.x() + self.defocus_v * disk_offset.y() + self.center;
-        let ray_direction = (pixel_center - ray_origin).unit();
-        
-        // println!("{:?}", Ray::new(pixel_center, ray_direction));
+        let (ray_origin, ray_direction) = self.project(u_norm, v_norm, disk_offset);
+        let time = if self.shutter_close > self.shutter_open {
+            thread_rng().gen_range(self.shutter_open..self.shutter_close)
+        } else {
+            self.shutter_open
+        };
 
-        Ray::new(ray_origin, ray_direction)
+        (Ray::new_with_time(ray_origin, ray_direction, time), dx, dy)
     }
 
     pub fn image_width(&self) -> usize {
@@ -94,16 +198,145 @@ impl Camera {
     pub fn image_height(&self) -> usize {
         self.image_height
     }
+
+    /// Clones this camera pinned to a new shutter interval -- `open == close`
+    /// pins every ray to that single instant, which is what `render_sequence`
+    /// uses to evaluate a time-parameterized scene (e.g. `MovingSphere`) at
+    /// one `t` per animation frame instead of across a motion-blur exposure.
+    pub fn with_shutter(&self, shutter_open: f64, shutter_close: f64) -> Camera {
+        Camera { shutter_open, shutter_close, ..self.clone() }
+    }
+
+    /// Clones this camera re-aimed at a new `center`/`look_at`, keeping
+    /// viewport size, defocus radius, and every other parameter fixed --
+    /// what `render_sequence` uses to fly the camera through an `Animation`'s
+    /// keyframes one frame at a time.
+    pub fn with_view(&self, center: Point3, look_at: Point3) -> Camera {
+        let viewport_width = self.viewport_u.length();
+        let viewport_height = self.viewport_v.length();
+        let defocus_radius = self.defocus_u.length();
+
+        let direction = (look_at - center).unit();
+        let w = -direction;
+        let u = self.up.cross(&w).unit();
+        let v = w.cross(&u);
+
+        let viewport_u = u * viewport_width;
+        let viewport_v = -v * viewport_height;
+        let viewport_upper_left_corner = center - w * self.focal_length - viewport_u / 2.0 - viewport_v / 2.0;
+
+        let defocus_u = defocus_radius * u;
+        let defocus_v = defocus_radius * v;
+
+        Camera {
+            center,
+            viewport_upper_left_corner,
+            viewport_u,
+            viewport_v,
+            defocus_u,
+            defocus_v,
+            u,
+            v,
+            w,
+            ..self.clone()
+        }
+    }
+}
+
+/// One waypoint in an `Animation`'s camera fly-through: at normalized time
+/// `t` (`0.0` is the first frame, `1.0` is the last), the camera should be at
+/// `center` looking at `look_at`.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub struct AnimationKeyframe {
+    pub t: f64,
+    pub center: Point3,
+    pub look_at: Point3,
+}
+
+/// Describes a camera fly-through as a sorted list of keyframes, linearly
+/// interpolated between the two that bracket a given frame's `t`. Built from
+/// an `[[animation.keyframes]]` array in the scene/camera toml so frame
+/// count, frame rate, and per-frame camera motion all live alongside the
+/// rest of the camera config.
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Animation {
+    keyframes: Vec<AnimationKeyframe>,
+}
+
+impl Animation {
+    /// Parses an `animation` table with a `keyframes` array of
+    /// `{ t, center, look_at }` entries (`t` in `[0.0, 1.0]`, sorted
+    /// ascending). Returns `None` if the table or array is missing.
+    pub fn from_toml(table: &toml::Table) -> Option<Animation> {
+        let animation_table = table.get("animation")?.as_table()?;
+        let keyframes_toml = animation_table.get("keyframes")?.as_array()?;
+
+        let keyframes = keyframes_toml.iter().map(|entry| {
+            let entry = entry.as_table().unwrap();
+            AnimationKeyframe {
+                t: entry["t"].as_float().unwrap(),
+                center: Vec3::from_toml(&entry["center"]).unwrap(),
+                look_at: Vec3::from_toml(&entry["look_at"]).unwrap(),
+            }
+        }).collect();
+
+        Some(Animation { keyframes })
+    }
+
+    /// Linearly interpolates `center`/`look_at` at normalized time `t`,
+    /// clamping to the first/last keyframe outside their range.
+    pub fn sample(&self, t: f64) -> (Point3, Point3) {
+        let first = self.keyframes.first().expect("Animation must have at least one keyframe");
+        let last = self.keyframes.last().unwrap();
+
+        if t <= first.t {
+            return (first.center, first.look_at);
+        }
+        if t >= last.t {
+            return (last.center, last.look_at);
+        }
+
+        let next_index = self.keyframes.iter().position(|k| k.t > t).unwrap();
+        let prev = &self.keyframes[next_index - 1];
+        let next = &self.keyframes[next_index];
+
+        let span = next.t - prev.t;
+        let fraction = if span > 0.0 { (t - prev.t) / span } else { 0.0 };
+
+        (
+            prev.center + (next.center - prev.center) * fraction,
+            prev.look_at + (next.look_at - prev.look_at) * fraction,
+        )
+    }
+}
+
+/// Per-lane AOV output of `Scene::trace_passes`: the usual beauty color
+/// alongside the world-space normal, hit `t`, and ambient-occlusion term
+/// recorded at each ray's first non-sky hit, so a compositor or denoiser can
+/// consume them as separate images instead of only the final color.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub struct RenderPasses<const N: usize>
+where LaneCount<N>: SupportedLaneCount
+{
+    pub beauty: PackedColor<N>,
+    pub normal: PackedVec3<N>,
+    pub depth: Simd<f64, N>,
+    pub ao: Simd<f64, N>,
 }
 
 #[derive(Debug)]
 #[derive(Clone)]
 pub struct Scene {
-    objects: Vec<Object>
+    bvh: Bvh,
+    lights: Vec<Light>,
 }
 
 struct RayState {
     color: Color,
+    emitted: Color,
     ray: Option<Ray>
 }
 
@@ -213,22 +446,89 @@ impl <const N: usize> CombinedIndex<N> {
 
 impl Scene {
     pub fn new() -> Scene {
-        Scene { objects: Vec::new() }
+        Scene { bvh: Bvh::build(Vec::new()), lights: Vec::new() }
     }
 
     pub fn from_list(list: &[Object]) -> Scene {
-        Scene { objects: list.to_vec()}
+        Scene { bvh: Bvh::build(list.to_vec()), lights: Vec::new() }
+    }
+
+    pub fn from_list_with_lights(list: &[Object], lights: &[Light]) -> Scene {
+        Scene { bvh: Bvh::build(list.to_vec()), lights: lights.to_vec() }
     }
 
     pub fn add(&mut self, object: Object) {
-        self.objects.push(object);
+        self.bvh.push(object);
+    }
+
+    pub fn add_light(&mut self, light: Light) {
+        self.lights.push(light);
+    }
+
+    pub fn len(&self) -> usize {
+        self.bvh.len()
     }
 
-    
     pub fn hit(&self, ray: &Ray, t_range: std::ops::Range<f64>) -> Option<HitRecord> {
-        self.objects.iter().map(|obj| {
-            obj.hit(ray, &t_range)
-        }).filter_map(|e| e).min_by_key(|h| {ordered_float::OrderedFloat::from(h.t())})
+        self.bvh.hit(ray, &t_range)
+    }
+
+    /// Blinn-Phong direct-lighting contribution at a hit, summed over every
+    /// light with a shadow ray (`Object::hit` up to the light's distance) zeroing
+    /// out occluded lights. `view_direction` points from the hit back toward the viewer.
+    pub fn direct_light(&self, hit_record: &HitRecord, view_direction: Vec3) -> Color {
+        let material = hit_record.material();
+        let base_color = material.diffuse_color();
+        let shininess = material.shininess();
+        let normal = hit_record.normal();
+        let location = hit_record.location();
+
+        self.lights.iter().fold(Color::black(), |accumulated, light| {
+            let (light_direction, light_distance, intensity) = light.sample(location);
+
+            let shadow_ray = Ray::new(location, light_direction);
+            if self.hit(&shadow_ray, 0.001..(light_distance - 0.001)).is_some() {
+                return accumulated;
+            }
+
+            let diffuse_factor = normal.dot(&light_direction).max(0.0);
+            let half_vector = (light_direction + view_direction).unit();
+            let specular_factor = normal.dot(&half_vector).max(0.0).powf(shininess);
+
+            accumulated + base_color * intensity * diffuse_factor + intensity * specular_factor
+        })
+    }
+
+    /// Alternate top-level shader: adds a deterministic Blinn-Phong direct-lighting
+    /// term at every hit on top of the existing Monte-Carlo path tracing, so a scene
+    /// can mix cheap highlights with `trace`'s noisy global illumination.
+    pub fn trace_direct(&self, ray: &Ray, depth_limit: usize) -> Color {
+        if depth_limit == 0 {
+            return Color::black();
+        }
+
+        match self.hit(ray, 0.001..f64::INFINITY) {
+            Some(hit_record) => {
+                let direct = self.direct_light(&hit_record, -ray.direction());
+                let hit_result = hit_record.hit_result(ray);
+
+                let indirect = match hit_result.scattered_ray() {
+                    Some(scattered_ray) => self.trace_direct(scattered_ray, depth_limit - 1) * hit_result.attenuation(),
+                    None => hit_result.attenuation(),
+                };
+
+                hit_result.emitted() + direct + indirect
+            },
+            None => {
+                let direction = ray.direction();
+                let a = 0.5 * (direction.y() + 1.0);
+                Color::new(
+                    (1.0 - a) + a * 0.5,
+                    (1.0 - a) + a * 0.7,
+                    (1.0 - a) + a * 1.0
+                )
+            },
+        }
     }
 
     pub fn trace(&self, ray: &Ray, depth_limit: usize) -> Color {
@@ -238,12 +538,14 @@ impl Scene {
             match self.hit(ray, 0.001..f64::INFINITY) {
                 Some(hit_record) => {
                     let hit_result = hit_record.hit_result(ray);
-                    match hit_result.scattered_ray() {
+                    let scattered = match hit_result.scattered_ray() {
                         Some(scattered_ray) => {
                             self.trace(scattered_ray, depth_limit - 1) * hit_result.attenuation()
                         },
                         None => hit_result.attenuation(),
-                    }
+                    };
+
+                    hit_result.emitted() + scattered
                 },
                 None => {
                     let direction = ray.direction();
@@ -262,6 +564,7 @@ impl Scene {
         let mut ray_stats = rays.iter().map(|r| {
             RayState {
                 color: Color::white(),
+                emitted: Color::black(),
                 ray: Some(*r)
             }
         }).collect::<Vec<_>>();
@@ -274,6 +577,7 @@ impl Scene {
                         match self.hit(&ray, 0.001..f64::INFINITY) {
                             Some(hit_record) => {
                                 let hit_result = hit_record.hit_result(&ray);
+                                ray_state.emitted = ray_state.emitted + ray_state.color * hit_result.emitted();
                                 ray_state.color = ray_state.color * hit_result.attenuation();
                                 ray_state.ray = hit_result.scattered_ray().copied();
                             },
@@ -297,7 +601,7 @@ impl Scene {
         ray_stats.iter().map(|rs| {
             match rs.ray {
                 Some(_) => Color::black(),
-                None => rs.color,
+                None => rs.emitted + rs.color,
             }
         }).collect()
     }
@@ -310,6 +614,7 @@ impl Scene {
     {
         let mut color = PackedColor::<N>::broadcast_scaler(Color::black());
         color.assign_masked(PackedColor::broadcast_scaler(Color::white()), rays.enabled());
+        let mut emitted = PackedColor::<N>::broadcast_scaler(Color::black());
         let mut hit_sky = PackedF64Mask::<N>::broadcast_bool(false);
 
         for _ in 0..depth_limit {
@@ -320,9 +625,7 @@ impl Scene {
 
             let mut hit_records = PackedHitRecords::<N>::default();
 
-            for object in &self.objects {
-                object.hit_packed(&rays, &(0.001..f64::INFINITY), &mut hit_records)
-            }
+            self.bvh.hit_packed(&rays, &(0.001..f64::INFINITY), &mut hit_records);
 
             hit_records.finalize(&rays);
 
@@ -332,6 +635,7 @@ impl Scene {
                 match hit_records.at(i) {
                     Some(hit_record) => {
                         let hit_result = hit_record.hit_result(&(rays.at(i).unwrap()));
+                        emitted.update(emitted.at(i) + color.at(i) * hit_result.emitted(), i);
                         color.update(color.at(i) * hit_result.attenuation(), i);
                         match hit_result.scattered_ray() {
                             Some(ray) => {
@@ -359,8 +663,107 @@ impl Scene {
         let sky_color = sky_color_part_1 + sky_color_part_2;
         color.assign_masked(color * sky_color, hit_sky);
         color.assign_masked(PackedColor::<N>::broadcast_scaler(Color::black()), rays.enabled());
+        emitted.assign_masked(PackedColor::<N>::broadcast_scaler(Color::black()), rays.enabled());
+
+        emitted + color
+    }
+
+    /// Like `trace_vectorized`, but alongside the beauty color also records
+    /// the world-space normal, hit `t`, and an ambient-occlusion term at
+    /// each ray's first non-sky hit. AO is estimated by casting
+    /// `ao_samples` cosine-weighted rays (the same `random_unit_vector() +
+    /// normal` trick `Lambertian::scatter` uses) over the hemisphere around
+    /// the shading normal, testing each out to `ao_max_distance`, and
+    /// recording the fraction that reach nothing (1.0 = fully open).
+    pub fn trace_passes<const N: usize>(
+        &self,
+        mut rays: PackedRays<N>,
+        depth_limit: usize,
+        ao_samples: usize,
+        ao_max_distance: f64,
+    ) -> RenderPasses<N>
+    where LaneCount<N>: SupportedLaneCount
+    {
+        let mut color = PackedColor::<N>::broadcast_scaler(Color::black());
+        color.assign_masked(PackedColor::broadcast_scaler(Color::white()), rays.enabled());
+        let mut emitted = PackedColor::<N>::broadcast_scaler(Color::black());
+        let mut hit_sky = PackedF64Mask::<N>::broadcast_bool(false);
+
+        let mut normal = PackedVec3::<N>::zeros();
+        let mut depth = Simd::<f64, N>::splat(f64::INFINITY);
+        let mut ao = Simd::<f64, N>::splat(1.0);
+        let mut recorded_first_hit = [false; N];
+
+        for _ in 0..depth_limit {
+            if !rays.any_enabled() {
+                // all rays have ended
+                break;
+            }
+
+            let mut hit_records = PackedHitRecords::<N>::default();
+
+            self.bvh.hit_packed(&rays, &(0.001..f64::INFINITY), &mut hit_records);
+
+            hit_records.finalize(&rays);
+
+            for i in 0..N {
+                match hit_records.at(i) {
+                    Some(hit_record) => {
+                        if !recorded_first_hit[i] {
+                            normal.update(i, hit_record.normal());
+                            depth[i] = hit_record.t();
+
+                            let location = hit_record.location();
+                            let hit_normal = hit_record.normal();
+                            let occluded_count = (0..ao_samples).filter(|_| {
+                                let sample_direction = (hit_normal + Vec3::random_unit_vector()).unit();
+                                let ao_ray = Ray::new(location, sample_direction);
+                                self.hit(&ao_ray, 0.001..ao_max_distance).is_some()
+                            }).count();
+                            ao[i] = if ao_samples == 0 {
+                                1.0
+                            } else {
+                                1.0 - (occluded_count as f64) / (ao_samples as f64)
+                            };
+
+                            recorded_first_hit[i] = true;
+                        }
+
+                        let hit_result = hit_record.hit_result(&(rays.at(i).unwrap()));
+                        emitted.update(emitted.at(i) + color.at(i) * hit_result.emitted(), i);
+                        color.update(color.at(i) * hit_result.attenuation(), i);
+                        match hit_result.scattered_ray() {
+                            Some(ray) => {
+                                rays.update(i, *ray);
+                            },
+                            None => {
+                                rays.disable(i);
+                            },
+                        }
+                    },
+                    None => {
+                        rays.disable(i);
+                        hit_sky[i] = <f64 as Scaler>::MaskType::mask_from_bool(true);
+                    },
+                }
+            }
+        }
+
+        // apply sky color to those rays that didn't hit the sky
+        let a = (rays.directions().y() + 1.0) * 0.5;
+        let sky_color_part_1 = PackedColor::<N>::broadcast_scaler(Color::white()) * (-a + 1.0);
+        let sky_color_part_2 = PackedColor::<N>::broadcast_scaler(Color::new(0.5, 0.7, 1.0)) * a;
+        let sky_color = sky_color_part_1 + sky_color_part_2;
+        color.assign_masked(color * sky_color, hit_sky);
+        color.assign_masked(PackedColor::<N>::broadcast_scaler(Color::black()), rays.enabled());
+        emitted.assign_masked(PackedColor::<N>::broadcast_scaler(Color::black()), rays.enabled());
 
-        color
+        RenderPasses {
+            beauty: emitted + color,
+            normal,
+            depth,
+            ao,
+        }
     }
 
     pub fn trace_vectorized2<const N: usize> (
@@ -370,6 +773,7 @@ impl Scene {
     ) -> Color {
         let mut ray_buffers: [Vec<PackedRays<N>>; 2] = [rays.to_vec(), vec![PackedRays::<N>::new(PackedVec3::default(), PackedVec3::default()); rays.len()]];
         let mut color:[Vec<PackedColor<N>>; 2] = array![vec![PackedColor::<N>::broadcast_scaler(Color::white()); rays.len()]; 2];
+        let mut emitted: [Vec<PackedColor<N>>; 2] = array![vec![PackedColor::<N>::broadcast_scaler(Color::black()); rays.len()]; 2];
         let mut hit_sky: [Vec<PackedF64Mask<N>>; 2] = array![vec![PackedF64Mask::<N>::broadcast_bool(false); rays.len()]; 2];
 
         let mut last_active_chunk = rays.len(); // set to one above the last active chunk
@@ -385,9 +789,7 @@ impl Scene {
             for j in 0..last_active_chunk {
                 let mut hit_records = PackedHitRecords::<N>::default();
 
-                for object in &self.objects {
-                    object.hit_packed(&ray_buffers[selector][j], &(0.001..f64::INFINITY), &mut hit_records)
-                }
+                self.bvh.hit_packed(&ray_buffers[selector][j], &(0.001..f64::INFINITY), &mut hit_records);
 
                 hit_records.finalize(&ray_buffers[selector][j]);
                 // let mut attenuations = PackedColor::<N>::broadcast_scaler(Color::white());
@@ -396,6 +798,8 @@ impl Scene {
                     match hit_records.at(i) {
                         Some(hit_record) => {
                             let hit_result = hit_record.hit_result(&(ray_buffers[selector][j].at(i).unwrap()));
+                            let new_emitted = emitted[selector][j].at(i) + color[selector][j].at(i) * hit_result.emitted();
+                            emitted[selector][j].update(new_emitted, i);
                             let new_color = color[selector][j].at(i) * hit_result.attenuation();
                             color[selector][j].update(new_color, i);
                             match hit_result.scattered_ray() {
@@ -431,6 +835,8 @@ impl Scene {
                             ray_buffers[next_selector][output_chunk].update(output_slot, ray);
                             let tmp_color = color[selector][i].at(j);
                             color[next_selector][output_chunk].update(tmp_color, output_slot);
+                            let tmp_emitted = emitted[selector][i].at(j);
+                            emitted[next_selector][output_chunk].update(tmp_emitted, output_slot);
                             let tmp_hit_sky = hit_sky[selector][i][j];
                             hit_sky[next_selector][output_chunk][output_slot] = tmp_hit_sky;
 
@@ -456,6 +862,8 @@ impl Scene {
                         ray_buffers[next_selector][output_chunk].update_with_enable(output_slot, ray, false);
                         let tmp_color = color[selector][i].at(j);
                         color[next_selector][output_chunk].update(tmp_color, output_slot);
+                        let tmp_emitted = emitted[selector][i].at(j);
+                        emitted[next_selector][output_chunk].update(tmp_emitted, output_slot);
                         let tmp_hit_sky = hit_sky[selector][i][j];
                         hit_sky[next_selector][output_chunk][output_slot] = tmp_hit_sky;
 
@@ -483,11 +891,12 @@ impl Scene {
             let sky_color_result = color[selector][j] * sky_color;
             color[selector][j].assign_masked(sky_color_result, hit_sky[selector][j]);
             color[selector][j].assign_masked(PackedColor::<N>::broadcast_scaler(Color::black()), ray_buffers[selector][j].enabled());
+            emitted[selector][j].assign_masked(PackedColor::<N>::broadcast_scaler(Color::black()), ray_buffers[selector][j].enabled());
         }
 
         let mut sum_color = PackedColor::<N>::broadcast_scaler(Color::black());
-        for color_chunk in &color[selector] {
-            sum_color = sum_color +  *color_chunk;
+        for (color_chunk, emitted_chunk) in color[selector].iter().zip(emitted[selector].iter()) {
+            sum_color = sum_color + *color_chunk + *emitted_chunk;
         }
 
         sum_color.sum()
@@ -499,6 +908,7 @@ impl Scene {
         depth_limit: usize,
     ) -> Color {
         let mut color = vec![PackedColor::<N>::broadcast_scaler(Color::white()); rays.len()];
+        let mut emitted = vec![PackedColor::<N>::broadcast_scaler(Color::black()); rays.len()];
         let mut hit_sky = vec![PackedF64Mask::<N>::broadcast_bool(false); rays.len()];
 
         let mut last_active_chunk = rays.len(); // set to one above the last active chunk
@@ -512,9 +922,7 @@ impl Scene {
             for j in 0..last_active_chunk {
                 let mut hit_records = PackedHitRecords::<N>::default();
 
-                for object in &self.objects {
-                    object.hit_packed(&rays[j], &(0.001..f64::INFINITY), &mut hit_records)
-                }
+                self.bvh.hit_packed(&rays[j], &(0.001..f64::INFINITY), &mut hit_records);
 
                 hit_records.finalize(&rays[j]);
 
@@ -525,6 +933,8 @@ impl Scene {
                     match hit_records.at(i) {
                         Some(hit_record) => {
                             let hit_result = hit_record.hit_result(&(rays[j].at(i).unwrap()));
+                            let new_emitted = emitted[j].at(i) + color[j].at(i) * hit_result.emitted();
+                            emitted[j].update(new_emitted, i);
                             let new_color = color[j].at(i) * hit_result.attenuation();
                             color[j].update(new_color, i);
                             match hit_result.scattered_ray() {
@@ -582,6 +992,11 @@ impl Scene {
                 color[front_index.chuck_index].update(back_color, front_index.slot_index);
                 color[back_index.chuck_index].update(front_color, back_index.slot_index);
 
+                let front_emitted = emitted[front_index.chuck_index].at(front_index.slot_index);
+                let back_emitted = emitted[back_index.chuck_index].at(back_index.slot_index);
+                emitted[front_index.chuck_index].update(back_emitted, front_index.slot_index);
+                emitted[back_index.chuck_index].update(front_emitted, back_index.slot_index);
+
                 let front_hit_sky = hit_sky[front_index.chuck_index][front_index.slot_index];
                 let back_hit_sky = hit_sky[back_index.chuck_index][back_index.slot_index];
                 hit_sky[front_index.chuck_index][front_index.slot_index] = back_hit_sky;
@@ -600,14 +1015,15 @@ impl Scene {
             let sky_color_result = color[j] * sky_color;
             color[j].assign_masked(sky_color_result, hit_sky[j]);
             color[j].assign_masked(PackedColor::<N>::broadcast_scaler(Color::black()), rays[j].enabled());
+            emitted[j].assign_masked(PackedColor::<N>::broadcast_scaler(Color::black()), rays[j].enabled());
         }
 
         let mut sum_color = PackedColor::<N>::broadcast_scaler(Color::black());
-        for color_chunk in &color {
-            sum_color = sum_color +  *color_chunk;
+        for (color_chunk, emitted_chunk) in color.iter().zip(emitted.iter()) {
+            sum_color = sum_color + *color_chunk + *emitted_chunk;
         }
 
         sum_color.sum()
-    }   
+    }
 }
  
\ No newline at end of file
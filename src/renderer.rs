@@ -1,5 +1,9 @@
-use crate::ray_tracing::{Scene, Camera, PackedRays};
-use crate::color::{Color, PackedColor};
+use crate::ray_tracing::{Scene, Camera, PackedRays, Animation};
+use crate::color::{Color, PackedColor, ToneMap, TransferFunction, ColorSpace, ColorOp, apply_color_ops};
+use crate::filter::Filter;
+use crate::video::FrameSink;
+use crate::simd_width::WIDE;
+use crate::quantize::{BlockColorCount, BlockPalette, QuantizeConfig, quantize_block};
 use image::{Rgb, RgbImage};
 use itertools::Itertools;
 use rand_distr::uniform::UniformSampler;
@@ -8,16 +12,92 @@ use std::sync::Arc;
 use console::Term;
 use std::io::Write;
 
+/// Normal/depth/ambient-occlusion AOV images produced by `render_passes`,
+/// alongside (and independent of) the usual beauty render.
+pub struct RenderPassImages {
+    pub normal: RgbImage,
+    pub depth: RgbImage,
+    pub ao: RgbImage,
+}
+
+/// Renders the normal, depth and ambient-occlusion passes for the whole
+/// image in one single-threaded pass, built on `Scene::trace_passes`.
+/// Unlike `TileRenderer::render`, this isn't tiled across worker threads --
+/// it's meant as an opt-in auxiliary output alongside a normal render, not
+/// the primary render path.
+///
+/// `depth_max` is the hit distance that maps to full white in the depth
+/// image; sky (no hit) always maps to white. Each pixel averages
+/// `samples_per_pixel` primary rays, matching the beauty render's
+/// antialiasing, and every sample's ambient occlusion is estimated from
+/// `ao_samples` hemisphere rays cast out to `ao_max_distance`.
+pub fn render_passes(camera: &Camera, scene: &Scene, max_bounces: usize, samples_per_pixel: usize, ao_samples: usize, ao_max_distance: f64, depth_max: f64) -> RenderPassImages {
+    const N: usize = WIDE;
+    let width = camera.image_width();
+    let height = camera.image_height();
+
+    let mut normal_image = RgbImage::new(width as u32, height as u32);
+    let mut depth_image = RgbImage::new(width as u32, height as u32);
+    let mut ao_image = RgbImage::new(width as u32, height as u32);
+
+    for row in 0..height {
+        for col in 0..width {
+            let mut normal_sum = Color::black();
+            let mut depth_sum = 0.0;
+            let mut ao_sum = 0.0;
+            let mut sample_count = 0usize;
+
+            for chunk in &(0..samples_per_pixel).map(|_| camera.get_ray(col, row)).chunks(N) {
+                let rays: PackedRays<N> = chunk.collect();
+                let passes = scene.trace_passes(rays, max_bounces, ao_samples, ao_max_distance);
+
+                for lane in 0..N {
+                    if rays.is_enabled(lane) {
+                        let lane_normal = passes.normal.at(lane);
+                        normal_sum = normal_sum + Color::new(lane_normal.x(), lane_normal.y(), lane_normal.z());
+                        depth_sum += passes.depth[lane].min(depth_max);
+                        ao_sum += passes.ao[lane];
+                        sample_count += 1;
+                    }
+                }
+            }
+
+            let (normal_average, depth_average, ao_average) = if sample_count > 0 {
+                (normal_sum / (sample_count as f64), depth_sum / (sample_count as f64), ao_sum / (sample_count as f64))
+            } else {
+                (Color::black(), depth_max, 1.0)
+            };
+
+            let encoded_normal = (normal_average * 0.5 + Color::new(0.5, 0.5, 0.5)).tone_mapped_u8_array(ToneMap::Clamp, TransferFunction::Gamma(1.0));
+            let encoded_depth = ((depth_average / depth_max) * 255.0).clamp(0.0, 255.0) as u8;
+            let encoded_ao = (ao_average.clamp(0.0, 1.0) * 255.0) as u8;
+
+            normal_image.put_pixel(col as u32, row as u32, Rgb(encoded_normal));
+            depth_image.put_pixel(col as u32, row as u32, Rgb([encoded_depth; 3]));
+            ao_image.put_pixel(col as u32, row as u32, Rgb([encoded_ao; 3]));
+        }
+    }
+
+    RenderPassImages { normal: normal_image, depth: depth_image, ao: ao_image }
+}
+
 pub struct RenderStat{
     duration: std::time::Duration,
     pixels_rendered: usize,
     pixels_per_second: f64,
+    quantization_stat: Option<QuantizationStat>,
 }
 
 impl RenderStat {
     pub fn new(duration: std::time::Duration, pixels_rendered: usize) -> RenderStat {
         let pixels_per_second = (pixels_rendered as f64) / duration.as_secs_f64();
-        RenderStat { duration, pixels_rendered, pixels_per_second}
+        RenderStat { duration, pixels_rendered, pixels_per_second, quantization_stat: None }
+    }
+
+    pub fn new_with_quantization_stat(duration: std::time::Duration, pixels_rendered: usize, quantization_stat: QuantizationStat) -> RenderStat {
+        let mut stat = RenderStat::new(duration, pixels_rendered);
+        stat.quantization_stat = Some(quantization_stat);
+        stat
     }
 
     pub fn duration(&self) -> std::time::Duration {
@@ -31,17 +111,101 @@ impl RenderStat {
     pub fn pixels_per_second(&self) -> f64 {
         self.pixels_per_second
     }
+
+    pub fn quantization_stat(&self) -> Option<&QuantizationStat> {
+        self.quantization_stat.as_ref()
+    }
+}
+
+/// Palette usage summary for a render done with `TileRenderer::quantize` set,
+/// one block per render tile (see `quantize::quantize_block`).
+pub struct QuantizationStat {
+    block_color_count: BlockColorCount,
+    blocks_flat: usize,
+    blocks_quantized: usize,
+}
+
+impl QuantizationStat {
+    pub fn block_color_count(&self) -> BlockColorCount {
+        self.block_color_count
+    }
+
+    /// Blocks emitted as a single flat color (below `skip_threshold`, or
+    /// every block when `block_color_count` is `One`).
+    pub fn blocks_flat(&self) -> usize {
+        self.blocks_flat
+    }
+
+    /// Blocks whose palette has more than one color.
+    pub fn blocks_quantized(&self) -> usize {
+        self.blocks_quantized
+    }
 }
 
 
 
 pub trait Renderer {
     fn render(&self, max_bounces: usize, samples_per_pixel: usize, scene: &Arc<Scene>, camera: &Arc<Camera>) -> (RgbImage, RenderStat);
+
+    /// Renders `frame_count` frames of a time-parameterized scene (e.g. one
+    /// containing `MovingSphere`s), evaluating `t = frame_index / frame_count`
+    /// per frame, and streams each finished frame to `sink` rather than
+    /// collecting them in memory. `fps` is metadata for the sink/container,
+    /// not something that changes how frames are rendered. When `animation`
+    /// is set, each frame's camera is additionally re-aimed via
+    /// `Animation::sample`/`Camera::with_view`, producing a camera fly-through
+    /// instead of (or alongside) object motion blur.
+    fn render_sequence(&self, max_bounces: usize, samples_per_pixel: usize, scene: &Arc<Scene>, camera: &Arc<Camera>, frame_count: usize, fps: f64, animation: Option<&Animation>, sink: &mut dyn FrameSink) -> std::io::Result<RenderStat>;
+}
+
+/// Which per-tile kernel `TileRenderer` dispatches to.
+///
+/// This is a runtime choice rather than a compile-time one: `TileRenderer::new`
+/// uses the mode it is given, which `main` takes from `detect_best_mode`'s CPU
+/// feature probe unless the `--render-mode` CLI flag pins one explicitly. Note this is
+/// *not* the full multiversioning the crate would need to drop its nightly
+/// `portable_simd` requirement — `render_vectorized` is still built on
+/// `std::simd`/`#![feature(portable_simd)]` and only runs on nightly. What this
+/// does provide is a stable place to pin the scalar kernel for reproducibility
+/// or benchmarking, and a starting point for adding true SSE/AVX2/NEON target-
+/// feature clones of the scalar kernel later.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub enum TileRenderMode {
+    Scaler,
+    Vectorized,
+}
+
+/// Probes the running CPU for the feature the vectorized kernel relies on
+/// (AVX2, since the packed types use 4-wide `f64` lanes) and falls back to the
+/// scalar kernel when it's absent or the target isn't x86.
+pub fn detect_best_mode() -> TileRenderMode {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            TileRenderMode::Vectorized
+        } else {
+            TileRenderMode::Scaler
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        TileRenderMode::Scaler
+    }
 }
 
 pub struct TileRenderer {
     num_threads: NonZeroUsize,
     block_size: NonZeroUsize,
+    mode: TileRenderMode,
+    filter: Filter,
+    tone_map: ToneMap,
+    transfer: TransferFunction,
+    color_space: ColorSpace,
+    quantize: Option<QuantizeConfig>,
+    color_ops: Arc<Vec<ColorOp>>,
 }
 
 #[derive(Debug)]
@@ -61,11 +225,39 @@ struct TileRenderResult {
     block_index_y: usize,
     thread_id: usize,
     average_pixel_throughput: f64,
-    output: Vec<Rgb<u8>>   
+    output: Vec<Rgb<u8>>,
+    palette: Option<BlockPalette>,
 }
 
 impl TileRenderTask {
-    fn render(&self, camera: &Arc<Camera>, scene: &Arc<Scene>, max_bounces: usize, samples_per_pixel: usize, thread_id: usize) -> TileRenderResult {
+    /// Quantizes the valid (non-padding) region of a just-rendered tile in
+    /// place and returns its palette, or `None` if `quantize` wasn't
+    /// requested. `result` is `block_size x block_size`, but edge tiles only
+    /// fill their top-left `size_x x size_y` corner, so that's the only
+    /// region fed to `quantize_block`.
+    fn quantize_tile(&self, result: &mut [Rgb<u8>], quantize: Option<QuantizeConfig>) -> Option<BlockPalette> {
+        let config = quantize?;
+
+        let mut valid_pixels: Vec<Rgb<u8>> = Vec::with_capacity(self.size_x * self.size_y);
+        for j in 0..self.size_y {
+            for i in 0..self.size_x {
+                valid_pixels.push(result[j * self.block_size + i]);
+            }
+        }
+
+        let palette = quantize_block(&valid_pixels, &config);
+        let quantized_pixels = palette.to_pixels();
+
+        for j in 0..self.size_y {
+            for i in 0..self.size_x {
+                result[j * self.block_size + i] = quantized_pixels[j * self.size_x + i];
+            }
+        }
+
+        Some(palette)
+    }
+
+    fn render(&self, camera: &Arc<Camera>, scene: &Arc<Scene>, max_bounces: usize, samples_per_pixel: usize, thread_id: usize, filter: Filter, tone_map: ToneMap, transfer: TransferFunction, color_space: ColorSpace, quantize: Option<QuantizeConfig>, color_ops: &[ColorOp]) -> TileRenderResult {
         let mut result = vec![Rgb::<u8>([0, 0, 0]); self.block_size.pow(2)];
 
         let col_offset = self.block_index_x * self.block_size;
@@ -78,29 +270,35 @@ impl TileRenderTask {
                 let col = i + col_offset;
                 let row = j + row_offset;
 
-                let rays: Vec<_> = (0..samples_per_pixel).map(|_| camera.get_ray(col, row)).collect();
+                let samples: Vec<_> = (0..samples_per_pixel).map(|_| camera.get_ray_with_offset(col, row, filter.radius())).collect();
+                let rays: Vec<_> = samples.iter().map(|(ray, _, _)| *ray).collect();
+                let weights = samples.iter().map(|(_, dx, dy)| filter.weight(*dx, *dy));
 
-                let pixel = Color::average(
-                    scene.trace_rays(&rays, max_bounces)
+                let pixel = Color::weighted_average(
+                    weights.zip(scene.trace_rays(&rays, max_bounces))
                 );
+                let pixel = apply_color_ops(pixel, color_ops);
 
-                result[j * self.block_size + i] = Rgb(pixel.to_u8_array());
+                result[j * self.block_size + i] = Rgb(pixel.tone_mapped_output_u8_array(tone_map, transfer, color_space));
             }
         }
         let duration = std::time::Instant::now().duration_since(start);
         let pixels_per_second = ((self.size_x * self.size_y) as f64) / duration.as_secs_f64();
 
+        let palette = self.quantize_tile(&mut result, quantize);
+
         TileRenderResult {
             block_index_x: self.block_index_x,
             block_index_y: self.block_index_y,
             thread_id: thread_id,
             average_pixel_throughput: pixels_per_second,
-            output: result
+            output: result,
+            palette,
         }
     }
 
-    fn render_vectorized(&self, camera: &Arc<Camera>, scene: &Arc<Scene>, max_bounces: usize, samples_per_pixel: usize, thread_id: usize) -> TileRenderResult {
-        const N:usize = 4;
+    fn render_vectorized(&self, camera: &Arc<Camera>, scene: &Arc<Scene>, max_bounces: usize, samples_per_pixel: usize, thread_id: usize, tone_map: ToneMap, transfer: TransferFunction, color_space: ColorSpace, quantize: Option<QuantizeConfig>, color_ops: &[ColorOp]) -> TileRenderResult {
+        const N: usize = WIDE;
         let mut result = vec![Rgb::<u8>([0, 0, 0]); self.block_size.pow(2)];
 
         let col_offset = self.block_index_x * self.block_size;
@@ -112,7 +310,7 @@ impl TileRenderTask {
 
                 let col = i + col_offset;
                 let row = j + row_offset;
-                
+
                 let mut packed_color = PackedColor::<N>::broadcast_scaler(Color::black());
                 for chunk in &(0..samples_per_pixel).map(|_| camera.get_ray(col, row)).chunks(N) {
                     let rays: PackedRays<N> = chunk.collect();
@@ -122,24 +320,28 @@ impl TileRenderTask {
 
                 let sum_color = packed_color.sum();
                 let pixel = sum_color / (samples_per_pixel as f64);
+                let pixel = apply_color_ops(pixel, color_ops);
 
-                result[j * self.block_size + i] = Rgb(pixel.to_u8_array());
+                result[j * self.block_size + i] = Rgb(pixel.tone_mapped_output_u8_array(tone_map, transfer, color_space));
             }
         }
         let duration = std::time::Instant::now().duration_since(start);
         let pixels_per_second = ((self.size_x * self.size_y) as f64) / duration.as_secs_f64();
 
+        let palette = self.quantize_tile(&mut result, quantize);
+
         TileRenderResult {
             block_index_x: self.block_index_x,
             block_index_y: self.block_index_y,
             thread_id: thread_id,
             average_pixel_throughput: pixels_per_second,
-            output: result
+            output: result,
+            palette,
         }
     }
 
     fn render_vectorized2(&self, camera: &Arc<Camera>, scene: &Arc<Scene>, max_bounces: usize, samples_per_pixel: usize, thread_id: usize) -> TileRenderResult {
-        const N:usize = 4;
+        const N: usize = WIDE;
         let mut result = vec![Rgb::<u8>([0, 0, 0]); self.block_size.pow(2)];
 
         let col_offset = self.block_index_x * self.block_size;
@@ -171,10 +373,11 @@ impl TileRenderTask {
             block_index_y: self.block_index_y,
             thread_id: thread_id,
             average_pixel_throughput: pixels_per_second,
-            output: result
+            output: result,
+            palette: None,
         }
     }
-    
+
 }
 
 #[derive(Debug)]
@@ -192,13 +395,20 @@ enum TileRenderUpdates {
 }
 
 impl TileRenderer {
-    pub fn new(num_threads: Option<NonZeroUsize>, block_size: NonZeroUsize) -> Box<dyn Renderer> {
+    pub fn new(num_threads: Option<NonZeroUsize>, block_size: NonZeroUsize, mode: TileRenderMode, filter: Filter, tone_map: ToneMap, transfer: TransferFunction, color_space: ColorSpace, quantize: Option<QuantizeConfig>, color_ops: Vec<ColorOp>) -> Box<dyn Renderer> {
         Box::new(TileRenderer {
             num_threads: match num_threads {
                 Some(n) => n,
                 None => std::thread::available_parallelism().unwrap(),
             },
-            block_size
+            block_size,
+            mode,
+            filter,
+            tone_map,
+            transfer,
+            color_space,
+            quantize,
+            color_ops: Arc::new(color_ops),
         })
     }
 }
@@ -237,6 +447,13 @@ impl Renderer for TileRenderer {
             let thread_update_tx = update_tx.clone();
             let thread_camera = camera.clone();
             let thread_scene = scene.clone();
+            let thread_mode = self.mode;
+            let thread_filter = self.filter;
+            let thread_tone_map = self.tone_map;
+            let thread_transfer = self.transfer;
+            let thread_color_space = self.color_space;
+            let thread_quantize = self.quantize;
+            let thread_color_ops = self.color_ops.clone();
 
             std::thread::spawn(move || {
                 loop {
@@ -250,7 +467,10 @@ impl Renderer for TileRenderer {
                         block_index_y: task.block_index_y,
                     })).unwrap();
 
-                    let result = task.render_vectorized(&thread_camera, &thread_scene, max_bounces, samples_per_pixel, thread_id);
+                    let result = match thread_mode {
+                        TileRenderMode::Scaler => task.render(&thread_camera, &thread_scene, max_bounces, samples_per_pixel, thread_id, thread_filter, thread_tone_map, thread_transfer, thread_color_space, thread_quantize, &thread_color_ops),
+                        TileRenderMode::Vectorized => task.render_vectorized(&thread_camera, &thread_scene, max_bounces, samples_per_pixel, thread_id, thread_tone_map, thread_transfer, thread_color_space, thread_quantize, &thread_color_ops),
+                    };
 
                     thread_update_tx.send(TileRenderUpdates::End(result)).unwrap();
 
@@ -336,6 +556,24 @@ impl Renderer for TileRenderer {
         writeln!(term, "Rendering Completed!").unwrap_or_default();
         term.flush().unwrap_or_default();
 
+        let render_stat = match self.quantize {
+            Some(config) => {
+                let blocks_quantized = results.iter().filter(|r| matches!(&r.palette, Some(p) if p.colors.len() > 1)).count();
+                let blocks_flat = results.len() - blocks_quantized;
+
+                RenderStat::new_with_quantization_stat(
+                    duration,
+                    camera.image_height() * camera.image_width(),
+                    QuantizationStat {
+                        block_color_count: config.block_color_count,
+                        blocks_flat,
+                        blocks_quantized,
+                    },
+                )
+            },
+            None => RenderStat::new(duration, camera.image_height() * camera.image_width()),
+        };
+
         (
             RgbImage::from_fn(camera.image_width().try_into().unwrap(), camera.image_height().try_into().unwrap(), |col, row| {
                 let block_index_x = (col as usize) / self.block_size;
@@ -345,7 +583,37 @@ impl Renderer for TileRenderer {
 
                 results[block_index_x + block_index_y * width_in_blocks].output[intra_block_x + intra_block_y * self.block_size.get()]
             }),
-            RenderStat::new(duration, camera.image_height() * camera.image_width())
+            render_stat
         )
     }
+
+    fn render_sequence(&self, max_bounces: usize, samples_per_pixel: usize, scene: &Arc<Scene>, camera: &Arc<Camera>, frame_count: usize, fps: f64, animation: Option<&Animation>, sink: &mut dyn FrameSink) -> std::io::Result<RenderStat> {
+        let start = std::time::Instant::now();
+        let mut pixels_rendered = 0;
+
+        println!("Rendering {} frame sequence at {} fps...", frame_count, fps);
+
+        for frame_index in 0..frame_count {
+            let t = (frame_index as f64) / (frame_count as f64);
+            let frame_camera = camera.with_shutter(t, t);
+            let frame_camera = match animation {
+                Some(animation) => {
+                    let (center, look_at) = animation.sample(t);
+                    frame_camera.with_view(center, look_at)
+                },
+                None => frame_camera,
+            };
+            let frame_camera = Arc::new(frame_camera);
+
+            println!("Frame {} of {} (t = {:.3})", frame_index + 1, frame_count, t);
+            let (frame_image, frame_stat) = self.render(max_bounces, samples_per_pixel, scene, &frame_camera);
+
+            sink.write_frame(&frame_image)?;
+            pixels_rendered += frame_stat.pixels_rendered();
+        }
+
+        let duration = std::time::Instant::now().duration_since(start);
+
+        Ok(RenderStat::new(duration, pixels_rendered))
+    }
 }
\ No newline at end of file
@@ -0,0 +1,49 @@
+use std::simd::{LaneCount, SupportedLaneCount, Simd};
+use rand::prelude::*;
+
+/// Xorshift64 PRNG with `N` lanes advancing in lockstep, so a whole packet of
+/// samples can be drawn without any lane falling behind in a scalar rejection
+/// loop (as `Vec3::random_in_unit_disk` needs for its scalar counterpart).
+#[derive(Debug, Clone, Copy)]
+pub struct PackedRng<const N: usize>
+where LaneCount<N>: SupportedLaneCount
+{
+    state: Simd<u64, N>,
+}
+
+impl <const N: usize> PackedRng<N>
+where LaneCount<N>: SupportedLaneCount
+{
+    /// `seeds` must be nonzero in every lane -- xorshift never leaves zero.
+    pub fn new(seeds: Simd<u64, N>) -> PackedRng<N> {
+        PackedRng { state: seeds }
+    }
+
+    pub fn from_entropy() -> PackedRng<N> {
+        let mut rng = thread_rng();
+        let seeds = Simd::from_array(std::array::from_fn(|_| loop {
+            let seed: u64 = rng.gen();
+            if seed != 0 {
+                break seed;
+            }
+        }));
+
+        PackedRng { state: seeds }
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> Simd<u64, N> {
+        let mut x = self.state;
+        x ^= x << Simd::splat(13);
+        x ^= x >> Simd::splat(7);
+        x ^= x << Simd::splat(17);
+        self.state = x;
+        x
+    }
+
+    /// Uniform `[0, 1)` per lane, taken from the top 53 bits of `next_u64`.
+    #[inline]
+    pub fn next_unit(&mut self) -> Simd<f64, N> {
+        (self.next_u64() >> Simd::splat(11)).cast::<f64>() * Simd::splat(2f64.powi(-53))
+    }
+}
@@ -1,8 +1,19 @@
 use std::{simd::{SupportedLaneCount, LaneCount, Simd, Mask, SimdElement, MaskElement, cmp::SimdPartialOrd, cmp::SimdPartialEq, StdFloat}, ops::{RangeBounds, Bound, Neg}, mem::{size_of, MaybeUninit}, marker::PhantomData};
 
+/// Each intrinsic branch below is gated on `cfg!(target_feature = "...") ||
+/// is_x86_feature_detected!("...")` rather than the runtime check alone: when
+/// the crate is built with that feature pinned (e.g. `-C target-feature=+avx2`),
+/// `cfg!` resolves to a literal `true` and the `||` short-circuits away the
+/// dynamic probe entirely, leaving a branch-free intrinsic sequence. Binaries
+/// built for a generic baseline still fall back to the runtime check.
+///
+/// `N * size_of::<T>()` need not be an exact multiple of a register's width
+/// (`all_lane_counts` allows e.g. `N = 3` or `N = 12`), so the AVX2 branches
+/// only loop over the full 256-bit chunks that fit and fill any remaining
+/// lanes with the portable per-lane select instead of reading past them.
 #[inline]
 pub fn masked_select<T, M, const N: usize>(base: Simd<T, N>, other: Simd<T, N>, mask: Mask<M, N>) -> Simd<T, N>
-where 
+where
     T: SimdElement<Mask = M>,
     M: MaskElement,
     LaneCount<N>: SupportedLaneCount,
@@ -12,49 +23,208 @@ where
     use std::arch::x86::*;
     #[cfg(target_arch = "x86_64")]
     use std::arch::x86_64::*;
+    #[cfg(target_arch = "aarch64")]
+    use std::arch::aarch64::*;
 
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     {
-        if size_of::<T>() == 4 && N * size_of::<T>() >= 32 && is_x86_feature_detected!("avx2") {
+        if size_of::<T>() == 4 && N * size_of::<T>() >= 64 && (cfg!(target_feature = "avx512f") || is_x86_feature_detected!("avx512f")) {
+            let mut result: MaybeUninit<Simd<T,N>> = MaybeUninit::uninit();
+            unsafe {
+                let result_ptr: *mut f32 = std::mem::transmute(result.as_mut_ptr());
+                let result_t_ptr: *mut T = std::mem::transmute(result.as_mut_ptr());
+                let mask_ptr: *const i32 = std::mem::transmute(mask.to_int().as_array().as_ptr());
+                let base_ptr: *const f32 = std::mem::transmute(base.as_array().as_ptr());
+                let other_ptr: *const f32 = std::mem::transmute(other.as_array().as_ptr());
+
+                // As with the AVX2 branches below, N * size_of::<T>() need
+                // not be an exact multiple of a register's width, so only
+                // the full 512-bit chunks go through the intrinsic and any
+                // remainder lanes fall back to the portable per-lane select.
+                let lanes_per_chunk = 64 / size_of::<T>();
+                let full_chunks = N / lanes_per_chunk;
+
+                for i in 0..full_chunks {
+                    let mask_mm = _mm512_loadu_epi32(mask_ptr.wrapping_add(lanes_per_chunk * i));
+                    let base_mm = _mm512_loadu_ps(base_ptr.wrapping_add(lanes_per_chunk * i));
+                    let other_mm = _mm512_loadu_ps(other_ptr.wrapping_add(lanes_per_chunk * i));
+
+                    let k = _mm512_movepi32_mask(mask_mm);
+                    let result_mm = _mm512_mask_blend_ps(k, base_mm, other_mm);
+
+                    _mm512_storeu_ps(result_ptr.wrapping_add(lanes_per_chunk * i), result_mm);
+                }
+
+                for lane in (full_chunks * lanes_per_chunk)..N {
+                    *result_t_ptr.wrapping_add(lane) = if mask.test(lane) { other[lane] } else { base[lane] };
+                }
+
+                return result.assume_init()
+            }
+        }
+
+        if size_of::<T>() == 8 && N * size_of::<T>() >= 64 && (cfg!(target_feature = "avx512f") || is_x86_feature_detected!("avx512f")) {
+            let mut result: MaybeUninit<Simd<T,N>> = MaybeUninit::uninit();
+            unsafe {
+                let result_ptr: *mut f64 = std::mem::transmute(result.as_mut_ptr());
+                let result_t_ptr: *mut T = std::mem::transmute(result.as_mut_ptr());
+                let mask_ptr: *const i64 = std::mem::transmute(mask.to_int().as_array().as_ptr());
+                let base_ptr: *const f64 = std::mem::transmute(base.as_array().as_ptr());
+                let other_ptr: *const f64 = std::mem::transmute(other.as_array().as_ptr());
+
+                let lanes_per_chunk = 64 / size_of::<T>();
+                let full_chunks = N / lanes_per_chunk;
+
+                for i in 0..full_chunks {
+                    let mask_mm = _mm512_loadu_epi64(mask_ptr.wrapping_add(lanes_per_chunk * i));
+                    let base_mm = _mm512_loadu_pd(base_ptr.wrapping_add(lanes_per_chunk * i));
+                    let other_mm = _mm512_loadu_pd(other_ptr.wrapping_add(lanes_per_chunk * i));
+
+                    let k = _mm512_movepi64_mask(mask_mm);
+                    let result_mm = _mm512_mask_blend_pd(k, base_mm, other_mm);
+
+                    _mm512_storeu_pd(result_ptr.wrapping_add(lanes_per_chunk * i), result_mm);
+                }
+
+                for lane in (full_chunks * lanes_per_chunk)..N {
+                    *result_t_ptr.wrapping_add(lane) = if mask.test(lane) { other[lane] } else { base[lane] };
+                }
+
+                return result.assume_init()
+            }
+        }
+
+        if size_of::<T>() == 4 && N * size_of::<T>() >= 32 && (cfg!(target_feature = "avx2") || is_x86_feature_detected!("avx2")) {
             let mut result: MaybeUninit<Simd<T,N>> = MaybeUninit::uninit();
             unsafe {
                 let result_ptr: *mut f32 = std::mem::transmute(result.as_mut_ptr());
+                let result_t_ptr: *mut T = std::mem::transmute(result.as_mut_ptr());
                 let mask_ptr: *const f32 = std::mem::transmute(mask.to_int().as_array().as_ptr());
                 let base_ptr: *const f32 = std::mem::transmute(base.as_array().as_ptr());
                 let other_ptr: *const f32 = std::mem::transmute(other.as_array().as_ptr());
-                for i in 0..(32 / N / size_of::<T>()) {
-                    let mask_mm = _mm256_load_ps(mask_ptr.wrapping_add((32 / 4) * i));
-                    let base_mm = _mm256_load_ps(base_ptr.wrapping_add((32 / 4) * i));
-                    let other_mm = _mm256_load_ps(other_ptr.wrapping_add((32 / 4) * i));
+
+                // N * size_of::<T>() isn't guaranteed to be an exact multiple of
+                // 32 (e.g. N = 3, 6, 12 under `all_lane_counts`), so only the
+                // full 256-bit chunks go through the intrinsic; any remainder
+                // lanes fall back to the portable per-lane select below.
+                let lanes_per_chunk = 32 / size_of::<T>();
+                let full_chunks = N / lanes_per_chunk;
+
+                for i in 0..full_chunks {
+                    let mask_mm = _mm256_loadu_ps(mask_ptr.wrapping_add(lanes_per_chunk * i));
+                    let base_mm = _mm256_loadu_ps(base_ptr.wrapping_add(lanes_per_chunk * i));
+                    let other_mm = _mm256_loadu_ps(other_ptr.wrapping_add(lanes_per_chunk * i));
 
                     let result_mm = _mm256_blendv_ps(base_mm, other_mm, mask_mm);
 
-                    _mm256_store_ps(result_ptr.wrapping_add((32 / 4) * i), result_mm);
+                    _mm256_storeu_ps(result_ptr.wrapping_add(lanes_per_chunk * i), result_mm);
+                }
+
+                for lane in (full_chunks * lanes_per_chunk)..N {
+                    *result_t_ptr.wrapping_add(lane) = if mask.test(lane) { other[lane] } else { base[lane] };
                 }
 
                 return result.assume_init()
             }
         }
 
-        if size_of::<T>() == 8 && N * size_of::<T>() >= 32 && is_x86_feature_detected!("avx2") {
+        if size_of::<T>() == 8 && N * size_of::<T>() >= 32 && (cfg!(target_feature = "avx2") || is_x86_feature_detected!("avx2")) {
             let mut result: MaybeUninit<Simd<T,N>> = MaybeUninit::uninit();
             unsafe {
                 let result_ptr: *mut f64 = std::mem::transmute(result.as_mut_ptr());
+                let result_t_ptr: *mut T = std::mem::transmute(result.as_mut_ptr());
                 let mask_ptr: *const f64 = std::mem::transmute(mask.to_int().as_array().as_ptr());
                 let base_ptr: *const f64 = std::mem::transmute(base.as_array().as_ptr());
                 let other_ptr: *const f64 = std::mem::transmute(other.as_array().as_ptr());
-                for i in 0..(32 / N / size_of::<T>()) {
-                    let mask_mm = _mm256_load_pd(mask_ptr.wrapping_add((32 / 8) * i));
-                    let base_mm = _mm256_load_pd(base_ptr.wrapping_add((32 / 8) * i));
-                    let other_mm = _mm256_load_pd(other_ptr.wrapping_add((32 / 8) * i));
+
+                let lanes_per_chunk = 32 / size_of::<T>();
+                let full_chunks = N / lanes_per_chunk;
+
+                for i in 0..full_chunks {
+                    let mask_mm = _mm256_loadu_pd(mask_ptr.wrapping_add(lanes_per_chunk * i));
+                    let base_mm = _mm256_loadu_pd(base_ptr.wrapping_add(lanes_per_chunk * i));
+                    let other_mm = _mm256_loadu_pd(other_ptr.wrapping_add(lanes_per_chunk * i));
 
                     let result_mm = _mm256_blendv_pd(base_mm, other_mm, mask_mm);
                     // let base_masked_mm = _mm256_andnot_pd(mask_mm, base_mm);
                     // let other_masked_mm = _mm256_and_pd(mask_mm, other_mm);
                     // let result_mm = _mm256_or_pd(base_masked_mm, other_masked_mm);
 
-                    _mm256_store_pd(result_ptr.wrapping_add((32 / 8) * i), result_mm);
+                    _mm256_storeu_pd(result_ptr.wrapping_add(lanes_per_chunk * i), result_mm);
+                }
+
+                for lane in (full_chunks * lanes_per_chunk)..N {
+                    *result_t_ptr.wrapping_add(lane) = if mask.test(lane) { other[lane] } else { base[lane] };
+                }
+
+                return result.assume_init()
+            }
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        if size_of::<T>() == 4 && N * size_of::<T>() >= 16 && (cfg!(target_feature = "neon") || std::arch::is_aarch64_feature_detected!("neon")) {
+            let mut result: MaybeUninit<Simd<T,N>> = MaybeUninit::uninit();
+            unsafe {
+                let result_ptr: *mut f32 = std::mem::transmute(result.as_mut_ptr());
+                let result_t_ptr: *mut T = std::mem::transmute(result.as_mut_ptr());
+                let mask_ptr: *const u32 = std::mem::transmute(mask.to_int().as_array().as_ptr());
+                let base_ptr: *const f32 = std::mem::transmute(base.as_array().as_ptr());
+                let other_ptr: *const f32 = std::mem::transmute(other.as_array().as_ptr());
+
+                // As with the AVX2/AVX-512 branches above, N * size_of::<T>()
+                // need not be an exact multiple of a register's width, so
+                // only the full 128-bit chunks go through the intrinsic and
+                // any remainder lanes fall back to the portable per-lane
+                // select.
+                let lanes_per_chunk = 16 / size_of::<T>();
+                let full_chunks = N / lanes_per_chunk;
+
+                for i in 0..full_chunks {
+                    let mask_nv = vld1q_u32(mask_ptr.wrapping_add(lanes_per_chunk * i));
+                    let base_nv = vld1q_f32(base_ptr.wrapping_add(lanes_per_chunk * i));
+                    let other_nv = vld1q_f32(other_ptr.wrapping_add(lanes_per_chunk * i));
+
+                    let result_nv = vbslq_f32(mask_nv, other_nv, base_nv);
+
+                    vst1q_f32(result_ptr.wrapping_add(lanes_per_chunk * i), result_nv);
+                }
+
+                for lane in (full_chunks * lanes_per_chunk)..N {
+                    *result_t_ptr.wrapping_add(lane) = if mask.test(lane) { other[lane] } else { base[lane] };
                 }
+
+                return result.assume_init()
+            }
+        }
+
+        if size_of::<T>() == 8 && N * size_of::<T>() >= 16 && (cfg!(target_feature = "neon") || std::arch::is_aarch64_feature_detected!("neon")) {
+            let mut result: MaybeUninit<Simd<T,N>> = MaybeUninit::uninit();
+            unsafe {
+                let result_ptr: *mut f64 = std::mem::transmute(result.as_mut_ptr());
+                let result_t_ptr: *mut T = std::mem::transmute(result.as_mut_ptr());
+                let mask_ptr: *const u64 = std::mem::transmute(mask.to_int().as_array().as_ptr());
+                let base_ptr: *const f64 = std::mem::transmute(base.as_array().as_ptr());
+                let other_ptr: *const f64 = std::mem::transmute(other.as_array().as_ptr());
+
+                let lanes_per_chunk = 16 / size_of::<T>();
+                let full_chunks = N / lanes_per_chunk;
+
+                for i in 0..full_chunks {
+                    let mask_nv = vld1q_u64(mask_ptr.wrapping_add(lanes_per_chunk * i));
+                    let base_nv = vld1q_f64(base_ptr.wrapping_add(lanes_per_chunk * i));
+                    let other_nv = vld1q_f64(other_ptr.wrapping_add(lanes_per_chunk * i));
+
+                    let result_nv = vbslq_f64(mask_nv, other_nv, base_nv);
+
+                    vst1q_f64(result_ptr.wrapping_add(lanes_per_chunk * i), result_nv);
+                }
+
+                for lane in (full_chunks * lanes_per_chunk)..N {
+                    *result_t_ptr.wrapping_add(lane) = if mask.test(lane) { other[lane] } else { base[lane] };
+                }
+
                 return result.assume_init()
             }
         }
@@ -73,6 +243,128 @@ where
     *base = masked_select(*base, other, mask);
 }
 
+/// Reorders `values` by the runtime index vector `indices` -- `result[i] ==
+/// values[indices[i]]` -- the vectorized primitive behind compacting
+/// surviving rays after a bounce or sorting a packet's lanes by hit
+/// distance, either of which would otherwise need a scalar loop over
+/// `at()`-style extraction. On x86 with AVX2 and `N` exactly one 256-bit
+/// register of 4-byte elements, this lowers to `_mm256_permutevar8x32_ps`
+/// (which only permutes within a single register); every other width or
+/// element type falls back to the portable `Simd::gather_or`, using
+/// `values` itself as the out-of-range fallback since `indices` is expected
+/// to only ever hold in-bounds lanes.
+#[inline]
+pub fn swizzle_dynamic<T, const N: usize>(values: Simd<T, N>, indices: Simd<usize, N>) -> Simd<T, N>
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if size_of::<T>() == 4 && N == 8 && (cfg!(target_feature = "avx2") || is_x86_feature_detected!("avx2")) {
+            let mut result: MaybeUninit<Simd<T, N>> = MaybeUninit::uninit();
+            unsafe {
+                let result_ptr: *mut f32 = std::mem::transmute(result.as_mut_ptr());
+                let values_ptr: *const f32 = std::mem::transmute(values.as_array().as_ptr());
+
+                let index_array: [i32; 8] = std::array::from_fn(|i| indices[i] as i32);
+                let index_mm = _mm256_loadu_si256(index_array.as_ptr() as *const __m256i);
+                let values_mm = _mm256_loadu_ps(values_ptr);
+
+                let result_mm = _mm256_permutevar8x32_ps(values_mm, index_mm);
+
+                _mm256_storeu_ps(result_ptr, result_mm);
+
+                return result.assume_init()
+            }
+        }
+    }
+
+    Simd::gather_or(values.as_array(), indices, values)
+}
+
+/// As `swizzle_dynamic`, but treats each index's high bit as a "produce
+/// zero" flag rather than part of the index -- the `vpshufb` `SHUF_0`
+/// convention (an 8-bit index with its top bit set zeros that lane instead
+/// of selecting byte 0), generalized here to the top bit of `usize`. Lets a
+/// lane-compaction pass zero out the lanes it didn't fill in the same
+/// gather that reorders the rest, instead of a separate masked store.
+#[inline]
+pub fn masked_swizzle_dynamic<T, const N: usize>(values: Simd<T, N>, indices: Simd<usize, N>) -> Simd<T, N>
+where
+    T: SimdElement + Default,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let high_bit = 1usize << (usize::BITS - 1);
+    let zero_mask = (indices & Simd::splat(high_bit)).simd_eq(Simd::splat(high_bit));
+    let safe_indices = indices & Simd::splat(!high_bit);
+
+    let gathered = swizzle_dynamic(values, safe_indices);
+
+    masked_select(gathered, Simd::splat(T::default()), zero_mask.cast())
+}
+
+/// Per-lane `atan2`, since `Simd<f64, N>` has no built-in inverse trig.
+#[inline]
+pub fn simd_atan2<const N: usize>(y: Simd<f64, N>, x: Simd<f64, N>) -> Simd<f64, N>
+where LaneCount<N>: SupportedLaneCount
+{
+    Simd::from_array(std::array::from_fn(|i| y[i].atan2(x[i])))
+}
+
+/// Per-lane `asin`, since `Simd<f64, N>` has no built-in inverse trig.
+#[inline]
+pub fn simd_asin<const N: usize>(x: Simd<f64, N>) -> Simd<f64, N>
+where LaneCount<N>: SupportedLaneCount
+{
+    Simd::from_array(std::array::from_fn(|i| x[i].asin()))
+}
+
+/// Per-lane `sin`, since `Simd<f64, N>` has no built-in transcendentals.
+#[inline]
+pub fn simd_sin<const N: usize>(x: Simd<f64, N>) -> Simd<f64, N>
+where LaneCount<N>: SupportedLaneCount
+{
+    Simd::from_array(std::array::from_fn(|i| x[i].sin()))
+}
+
+/// Per-lane `cos`, since `Simd<f64, N>` has no built-in transcendentals.
+#[inline]
+pub fn simd_cos<const N: usize>(x: Simd<f64, N>) -> Simd<f64, N>
+where LaneCount<N>: SupportedLaneCount
+{
+    Simd::from_array(std::array::from_fn(|i| x[i].cos()))
+}
+
+/// Per-lane `exp`, since `Simd<f64, N>` has no built-in transcendentals.
+#[inline]
+pub fn simd_exp<const N: usize>(x: Simd<f64, N>) -> Simd<f64, N>
+where LaneCount<N>: SupportedLaneCount
+{
+    Simd::from_array(std::array::from_fn(|i| x[i].exp()))
+}
+
+/// Per-lane `ln`, since `Simd<f64, N>` has no built-in transcendentals.
+#[inline]
+pub fn simd_ln<const N: usize>(x: Simd<f64, N>) -> Simd<f64, N>
+where LaneCount<N>: SupportedLaneCount
+{
+    Simd::from_array(std::array::from_fn(|i| x[i].ln()))
+}
+
+/// Per-lane `powf`, since `Simd<f64, N>` has no built-in transcendentals.
+#[inline]
+pub fn simd_powf<const N: usize>(x: Simd<f64, N>, y: Simd<f64, N>) -> Simd<f64, N>
+where LaneCount<N>: SupportedLaneCount
+{
+    Simd::from_array(std::array::from_fn(|i| x[i].powf(y[i])))
+}
+
 pub enum BoundType {
     UPPER,
     LOWER,
@@ -168,8 +460,100 @@ where
             std::mem::transmute(self.inner_simd[index])
         }
     }
+
+    /// Reads the `U`-sized field `STRIDE` bytes into every non-null lane's
+    /// pointee in one vectorized gather (`STRIDE = 0` for a `T` whose first
+    /// field is itself a SIMD-loadable `U`), filling null lanes with
+    /// `default` instead of dereferencing them -- lets a packet's hit
+    /// records (e.g. distance, material pointer) be read without a scalar
+    /// loop over lanes. On AVX2, 8-byte fields go through
+    /// `_mm256_mask_i64gather_epi64` with the pointer itself as the gather
+    /// index (base address null, scale 1), the same null-base trick
+    /// `Packed::gather_f64`'s AVX2 path doesn't need since it gathers by
+    /// slice index rather than raw pointer.
+    #[inline]
+    pub fn gather<U, const STRIDE: usize>(&self, default: Simd<U, N>) -> Simd<U, N>
+    where
+        U: SimdElement,
+    {
+        let null_mask = self.inner_simd.simd_eq(Simd::splat(0));
+        let field_ptrs = self.inner_simd + Simd::splat(STRIDE);
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if size_of::<U>() == 8 && (cfg!(target_feature = "avx2") || is_x86_feature_detected!("avx2")) {
+                return unsafe { gather_ptr_avx2_8(field_ptrs, !null_mask, default) };
+            }
+        }
+
+        Simd::from_array(std::array::from_fn(|i| {
+            if null_mask.test(i) {
+                default[i]
+            } else {
+                unsafe { std::ptr::read(field_ptrs[i] as *const U) }
+            }
+        }))
+    }
 }
 
+/// AVX2 fast path for `PackedOptionalReference::gather` on 8-byte fields:
+/// gathers by treating each lane's pointer as its own gather index against a
+/// null base address (`scale = 1`), so `non_null_mask`-suppressed lanes (the
+/// null pointers) are never dereferenced and take their value from `default`
+/// instead, matching `_mm256_mask_i64gather_epi64`'s masked-gather semantics.
+///
+/// Each chunk gathers 4 lanes (32 bytes / 8-byte element), so -- as with
+/// `masked_select`'s AVX2 branches -- only the full chunks that fit in `N`
+/// go through the intrinsic; the remaining lanes are read with the same
+/// scalar pointer-read-or-default logic as the portable path in `gather`.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+unsafe fn gather_ptr_avx2_8<U, M, const N: usize>(field_ptrs: Simd<usize, N>, non_null_mask: Mask<M, N>, default: Simd<U, N>) -> Simd<U, N>
+where
+    U: SimdElement,
+    M: MaskElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    let mut result: MaybeUninit<Simd<U,N>> = MaybeUninit::uninit();
+    let result_ptr: *mut i64 = std::mem::transmute(result.as_mut_ptr());
+    let result_u_ptr: *mut U = std::mem::transmute(result.as_mut_ptr());
+    let offsets_ptr: *const i64 = std::mem::transmute(field_ptrs.as_array().as_ptr());
+    let gather_mask = non_null_mask.to_int();
+    let mask_ptr: *const i64 = std::mem::transmute(gather_mask.as_array().as_ptr());
+    let default_ptr: *const i64 = std::mem::transmute(default.as_array().as_ptr());
+
+    let lanes_per_chunk = 32 / 8;
+    let full_chunks = N / lanes_per_chunk;
+
+    for i in 0..full_chunks {
+        let offsets_mm = _mm256_loadu_si256(offsets_ptr.wrapping_add(lanes_per_chunk * i) as *const __m256i);
+        let mask_mm = _mm256_loadu_si256(mask_ptr.wrapping_add(lanes_per_chunk * i) as *const __m256i);
+        let src_mm = _mm256_loadu_si256(default_ptr.wrapping_add(lanes_per_chunk * i) as *const __m256i);
+
+        let gathered = _mm256_mask_i64gather_epi64(src_mm, std::ptr::null(), offsets_mm, mask_mm, 1);
+
+        _mm256_storeu_si256(result_ptr.wrapping_add(lanes_per_chunk * i) as *mut __m256i, gathered);
+    }
+
+    for lane in (full_chunks * lanes_per_chunk)..N {
+        let value = if non_null_mask.test(lane) {
+            std::ptr::read(field_ptrs[lane] as *const U)
+        } else {
+            default[lane]
+        };
+        *result_u_ptr.wrapping_add(lane) = value;
+    }
+
+    result.assume_init()
+}
+
+/// Gated the same way as `masked_select`'s intrinsic branches -- see its
+/// doc comment for why each check is `cfg!(target_feature = "...") ||
+/// is_x86_feature_detected!("...")` instead of just the latter.
 pub fn negate_simd_float<T, const N: usize>(value: Simd<T, N>) -> Simd<T, N>
 where
     LaneCount<N>: SupportedLaneCount,
@@ -180,37 +564,194 @@ where
     use std::arch::x86::*;
     #[cfg(target_arch = "x86_64")]
     use std::arch::x86_64::*;
+    #[cfg(target_arch = "aarch64")]
+    use std::arch::aarch64::*;
 
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     {
-        if size_of::<T>() == 4 && N * size_of::<T>() >= 32 && is_x86_feature_detected!("avx2") {
+        if size_of::<T>() == 4 && N * size_of::<T>() >= 64 && (cfg!(target_feature = "avx512f") || is_x86_feature_detected!("avx512f")) {
+            let mut result: MaybeUninit<Simd<T,N>> = MaybeUninit::uninit();
+            unsafe {
+                let result_ptr: *mut i32 = std::mem::transmute(result.as_mut_ptr());
+                let result_t_ptr: *mut T = std::mem::transmute(result.as_mut_ptr());
+                let input_ptr: *const i32 = std::mem::transmute(value.as_array().as_ptr());
+
+                // As with the AVX2 branches below, N * size_of::<T>() need
+                // not be an exact multiple of a register's width, so only
+                // the full 512-bit chunks go through the intrinsic and any
+                // remainder lanes fall back to the portable `-value` below.
+                let lanes_per_chunk = 64 / size_of::<T>();
+                let full_chunks = N / lanes_per_chunk;
+
+                for i in 0..full_chunks {
+                    let input_mm = _mm512_loadu_epi32(input_ptr.wrapping_add(lanes_per_chunk * i));
+                    let invert_mask_mm = _mm512_set1_epi32(0x8000_0000u32 as i32);
+
+                    let result_mm = _mm512_xor_epi32(input_mm, invert_mask_mm);
+                    _mm512_storeu_epi32(result_ptr.wrapping_add(lanes_per_chunk * i), result_mm);
+                }
+
+                if full_chunks * lanes_per_chunk < N {
+                    let negated = -value;
+                    for lane in (full_chunks * lanes_per_chunk)..N {
+                        *result_t_ptr.wrapping_add(lane) = negated[lane];
+                    }
+                }
+
+                return result.assume_init()
+            }
+        }
+
+        if size_of::<T>() == 8 && N * size_of::<T>() >= 64 && (cfg!(target_feature = "avx512f") || is_x86_feature_detected!("avx512f")) {
+            let mut result: MaybeUninit<Simd<T,N>> = MaybeUninit::uninit();
+            unsafe {
+                let result_ptr: *mut i64 = std::mem::transmute(result.as_mut_ptr());
+                let result_t_ptr: *mut T = std::mem::transmute(result.as_mut_ptr());
+                let input_ptr: *const i64 = std::mem::transmute(value.as_array().as_ptr());
+
+                let lanes_per_chunk = 64 / size_of::<T>();
+                let full_chunks = N / lanes_per_chunk;
+
+                for i in 0..full_chunks {
+                    let input_mm = _mm512_loadu_epi64(input_ptr.wrapping_add(lanes_per_chunk * i));
+                    let invert_mask_mm = _mm512_set1_epi64(0x8000_0000_0000_0000u64 as i64);
+
+                    let result_mm = _mm512_xor_epi64(input_mm, invert_mask_mm);
+                    _mm512_storeu_epi64(result_ptr.wrapping_add(lanes_per_chunk * i), result_mm);
+                }
+
+                if full_chunks * lanes_per_chunk < N {
+                    let negated = -value;
+                    for lane in (full_chunks * lanes_per_chunk)..N {
+                        *result_t_ptr.wrapping_add(lane) = negated[lane];
+                    }
+                }
+
+                return result.assume_init()
+            }
+        }
+
+        if size_of::<T>() == 4 && N * size_of::<T>() >= 32 && (cfg!(target_feature = "avx2") || is_x86_feature_detected!("avx2")) {
             let mut result: MaybeUninit<Simd<T,N>> = MaybeUninit::uninit();
             unsafe {
                 let result_ptr: *mut f32 = std::mem::transmute(result.as_mut_ptr());
+                let result_t_ptr: *mut T = std::mem::transmute(result.as_mut_ptr());
                 let input_ptr: *const f32 = std::mem::transmute(value.as_array().as_ptr());
-                for i in 0..(32 / N / size_of::<T>()) {
-                    let input_mm = _mm256_load_ps(input_ptr.wrapping_add((32 / 4) * i));
+
+                // As in `masked_select`'s AVX2 branches: only full 256-bit
+                // chunks go through the intrinsic; leftover lanes (N not a
+                // multiple of 8 floats) are negated with the portable `-value`
+                // below instead of reading past the end of the register.
+                let lanes_per_chunk = 32 / size_of::<T>();
+                let full_chunks = N / lanes_per_chunk;
+
+                for i in 0..full_chunks {
+                    let input_mm = _mm256_loadu_ps(input_ptr.wrapping_add(lanes_per_chunk * i));
                     let invert_mask_mm = _mm256_broadcast_ss(std::mem::transmute(&0x8000_0000u32));
 
                     let result_mm = _mm256_xor_ps(input_mm, invert_mask_mm);
-                    _mm256_store_ps(result_ptr.wrapping_add((32 / 4) * i), result_mm);
+                    _mm256_storeu_ps(result_ptr.wrapping_add(lanes_per_chunk * i), result_mm);
+                }
+
+                if full_chunks * lanes_per_chunk < N {
+                    let negated = -value;
+                    for lane in (full_chunks * lanes_per_chunk)..N {
+                        *result_t_ptr.wrapping_add(lane) = negated[lane];
+                    }
                 }
 
                 return result.assume_init()
             }
         }
 
-        if size_of::<T>() == 8 && N * size_of::<T>() >= 32 && is_x86_feature_detected!("avx2") {
+        if size_of::<T>() == 8 && N * size_of::<T>() >= 32 && (cfg!(target_feature = "avx2") || is_x86_feature_detected!("avx2")) {
             let mut result: MaybeUninit<Simd<T,N>> = MaybeUninit::uninit();
             unsafe {
                 let result_ptr: *mut f64 = std::mem::transmute(result.as_mut_ptr());
+                let result_t_ptr: *mut T = std::mem::transmute(result.as_mut_ptr());
                 let input_ptr: *const f64 = std::mem::transmute(value.as_array().as_ptr());
-                for i in 0..(32 / N / size_of::<T>()) {
-                    let input_mm = _mm256_load_pd(input_ptr.wrapping_add((32 / 8) * i));
+
+                let lanes_per_chunk = 32 / size_of::<T>();
+                let full_chunks = N / lanes_per_chunk;
+
+                for i in 0..full_chunks {
+                    let input_mm = _mm256_loadu_pd(input_ptr.wrapping_add(lanes_per_chunk * i));
                     let invert_mask_mm = _mm256_broadcast_sd(std::mem::transmute(&0x8000_0000_0000_0000u64));
 
                     let result_mm = _mm256_xor_pd(input_mm, invert_mask_mm);
-                    _mm256_store_pd(result_ptr.wrapping_add((32 / 8) * i), result_mm);
+                    _mm256_storeu_pd(result_ptr.wrapping_add(lanes_per_chunk * i), result_mm);
+                }
+
+                if full_chunks * lanes_per_chunk < N {
+                    let negated = -value;
+                    for lane in (full_chunks * lanes_per_chunk)..N {
+                        *result_t_ptr.wrapping_add(lane) = negated[lane];
+                    }
+                }
+
+                return result.assume_init()
+            }
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        if size_of::<T>() == 4 && N * size_of::<T>() >= 16 && (cfg!(target_feature = "neon") || std::arch::is_aarch64_feature_detected!("neon")) {
+            let mut result: MaybeUninit<Simd<T,N>> = MaybeUninit::uninit();
+            unsafe {
+                let result_ptr: *mut u32 = std::mem::transmute(result.as_mut_ptr());
+                let result_t_ptr: *mut T = std::mem::transmute(result.as_mut_ptr());
+                let input_ptr: *const u32 = std::mem::transmute(value.as_array().as_ptr());
+
+                // As with the AVX2/AVX-512 branches above, N * size_of::<T>()
+                // need not be an exact multiple of a register's width, so
+                // only the full 128-bit chunks go through the intrinsic and
+                // any remainder lanes fall back to the portable `-value`.
+                let lanes_per_chunk = 16 / size_of::<T>();
+                let full_chunks = N / lanes_per_chunk;
+
+                for i in 0..full_chunks {
+                    let input_nv = vld1q_u32(input_ptr.wrapping_add(lanes_per_chunk * i));
+                    let sign_bit_nv = vdupq_n_u32(0x8000_0000u32);
+
+                    let result_nv = veorq_u32(input_nv, sign_bit_nv);
+                    vst1q_u32(result_ptr.wrapping_add(lanes_per_chunk * i), result_nv);
+                }
+
+                if full_chunks * lanes_per_chunk < N {
+                    let negated = -value;
+                    for lane in (full_chunks * lanes_per_chunk)..N {
+                        *result_t_ptr.wrapping_add(lane) = negated[lane];
+                    }
+                }
+
+                return result.assume_init()
+            }
+        }
+
+        if size_of::<T>() == 8 && N * size_of::<T>() >= 16 && (cfg!(target_feature = "neon") || std::arch::is_aarch64_feature_detected!("neon")) {
+            let mut result: MaybeUninit<Simd<T,N>> = MaybeUninit::uninit();
+            unsafe {
+                let result_ptr: *mut u64 = std::mem::transmute(result.as_mut_ptr());
+                let result_t_ptr: *mut T = std::mem::transmute(result.as_mut_ptr());
+                let input_ptr: *const u64 = std::mem::transmute(value.as_array().as_ptr());
+
+                let lanes_per_chunk = 16 / size_of::<T>();
+                let full_chunks = N / lanes_per_chunk;
+
+                for i in 0..full_chunks {
+                    let input_nv = vld1q_u64(input_ptr.wrapping_add(lanes_per_chunk * i));
+                    let sign_bit_nv = vdupq_n_u64(0x8000_0000_0000_0000u64);
+
+                    let result_nv = veorq_u64(input_nv, sign_bit_nv);
+                    vst1q_u64(result_ptr.wrapping_add(lanes_per_chunk * i), result_nv);
+                }
+
+                if full_chunks * lanes_per_chunk < N {
+                    let negated = -value;
+                    for lane in (full_chunks * lanes_per_chunk)..N {
+                        *result_t_ptr.wrapping_add(lane) = negated[lane];
+                    }
                 }
 
                 return result.assume_init()
@@ -0,0 +1,62 @@
+use std::simd::{LaneCount, SupportedLaneCount, Simd};
+use crate::geometry::PackedVec3;
+
+/// Widest packet size the current build target can execute natively.
+#[cfg(target_feature = "avx512f")]
+pub const WIDE: usize = 8;
+#[cfg(all(not(target_feature = "avx512f"), target_feature = "avx2"))]
+pub const WIDE: usize = 4;
+#[cfg(not(any(target_feature = "avx512f", target_feature = "avx2")))]
+pub const WIDE: usize = 2;
+
+/// Fallback packet size for code paths that need a lane count guaranteed to
+/// fit in a single narrow register regardless of target (e.g. partial tail
+/// packets), as opposed to `WIDE`'s "use everything available" choice.
+pub const NARROW: usize = 2;
+
+/// Common `PackedVec3<N>` surface a renderer can be written against once and
+/// have it run at whatever lane width `WIDE`/`NARROW` resolve to for the
+/// build target, instead of hard-coding a specific `N`.
+pub trait PackedVec3Ops<const N: usize>:
+    Copy
+    + std::ops::Add<Output = Self>
+    + std::ops::Sub<Output = Self>
+    + std::ops::Mul<f64, Output = Self>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    fn dot(&self, rhs: &Self) -> Simd<f64, N>;
+    fn length(&self) -> Simd<f64, N>;
+    fn length_squared(&self) -> Simd<f64, N>;
+    fn unit_vector(&self) -> Self;
+    fn reflect(&self, normal: &Self) -> Self;
+    fn refract(&self, normal: &Self, refraction_ratio: Simd<f64, N>) -> Self;
+}
+
+impl <const N: usize> PackedVec3Ops<N> for PackedVec3<N>
+where LaneCount<N>: SupportedLaneCount
+{
+    fn dot(&self, rhs: &Self) -> Simd<f64, N> {
+        PackedVec3::dot(self, rhs)
+    }
+
+    fn length(&self) -> Simd<f64, N> {
+        PackedVec3::length(self)
+    }
+
+    fn length_squared(&self) -> Simd<f64, N> {
+        PackedVec3::length_squared(self)
+    }
+
+    fn unit_vector(&self) -> Self {
+        PackedVec3::unit_vector(self)
+    }
+
+    fn reflect(&self, normal: &Self) -> Self {
+        PackedVec3::reflect(self, normal)
+    }
+
+    fn refract(&self, normal: &Self, refraction_ratio: Simd<f64, N>) -> Self {
+        PackedVec3::refract(self, normal, refraction_ratio)
+    }
+}
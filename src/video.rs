@@ -0,0 +1,77 @@
+use image::RgbImage;
+use std::io::{self, Write};
+
+/// Destination for the frames `Renderer::render_sequence` produces, one at a
+/// time, as it renders an animation. Kept as a plain trait (rather than, say,
+/// collecting `Vec<RgbImage>`) so a whole sequence never needs to be held in
+/// memory at once -- a long fly-through can stream straight to disk.
+pub trait FrameSink {
+    fn write_frame(&mut self, frame: &RgbImage) -> io::Result<()>;
+}
+
+/// Writes frames as YUV4MPEG2 (Y4M), the trivially simple uncompressed video
+/// container most encoders (`ffmpeg`, `mpv`) can read directly via a pipe.
+/// Each frame's three channels are written as-is, as three full-resolution
+/// 8-bit planes (matching the `C444` no-chroma-subsampling tag in the stream
+/// header) -- this sink doesn't convert color spaces itself, so the renderer
+/// must be run with `--color-space ycbcr` (`Color::tone_mapped_output_u8_array`)
+/// for the planes to actually be BT.709 Y/Cb/Cr rather than RGB.
+pub struct Y4mSink<W: Write> {
+    writer: W,
+    width: usize,
+    height: usize,
+    fps: f64,
+    header_written: bool,
+}
+
+impl <W: Write> Y4mSink<W> {
+    pub fn new(writer: W, width: usize, height: usize, fps: f64) -> Y4mSink<W> {
+        Y4mSink { writer, width, height, fps, header_written: false }
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        // Y4M's F field is an exact num:den rational. Round `fps` to three
+        // decimal places -- enough to represent common fractional rates like
+        // 23.976, 29.97 and 59.94 -- instead of truncating to an integer,
+        // which would silently speed up playback for any non-integer fps.
+        let denominator = 1000u64;
+        let numerator = (self.fps * denominator as f64).round() as u64;
+        let divisor = gcd(numerator, denominator);
+
+        write!(self.writer, "YUV4MPEG2 W{} H{} F{}:{} Ip A1:1 C444\n", self.width, self.height, numerator / divisor, denominator / divisor)
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+impl <W: Write> FrameSink for Y4mSink<W> {
+    fn write_frame(&mut self, frame: &RgbImage) -> io::Result<()> {
+        if !self.header_written {
+            self.write_header()?;
+            self.header_written = true;
+        }
+
+        write!(self.writer, "FRAME\n")?;
+
+        let plane_size = self.width * self.height;
+        let mut first_plane = vec![0u8; plane_size];
+        let mut second_plane = vec![0u8; plane_size];
+        let mut third_plane = vec![0u8; plane_size];
+
+        for (index, pixel) in frame.pixels().enumerate() {
+            let [a, b, c] = pixel.0;
+
+            first_plane[index] = a;
+            second_plane[index] = b;
+            third_plane[index] = c;
+        }
+
+        self.writer.write_all(&first_plane)?;
+        self.writer.write_all(&second_plane)?;
+        self.writer.write_all(&third_plane)?;
+
+        Ok(())
+    }
+}